@@ -1,4 +1,45 @@
-use crate::prelude::*;
+use crate::{
+    entity::UsesItems,
+    game_mechanics::{
+        blast_radius, carried_stack, InInventory, NaniteCache, Stack, INVENTORY_CAPACITY,
+    },
+    prelude::*,
+};
+
+/// What a monster can take off the floor
+#[derive(SystemData)]
+pub struct Pickups<'a> {
+    entities: Entities<'a>,
+    in_inventories: ReadStorage<'a, InInventory>,
+    stacks: ReadStorage<'a, Stack>,
+    nanite_caches: ReadStorage<'a, NaniteCache>,
+}
+
+impl Pickups<'_> {
+    /// Monsters can't absorb nanites and run out of room like the player does,
+    /// so going after anything else would leave them standing on it forever.
+    fn can_carry(&self, item: Entity, carrier: Entity) -> bool {
+        if self.nanite_caches.contains(item) {
+            return false;
+        }
+
+        let carried = self
+            .in_inventories
+            .join()
+            .filter(|in_inventory| in_inventory.owner() == carrier)
+            .count();
+
+        carried < INVENTORY_CAPACITY
+            || carried_stack(
+                item,
+                carrier,
+                &self.entities,
+                &self.in_inventories,
+                &self.stacks,
+            )
+            .is_some()
+    }
+}
 
 pub struct MonsterAISystem;
 
@@ -9,10 +50,18 @@ impl<'a> System<'a> for MonsterAISystem {
         Entities<'a>,
         Intents<'a>,
         EffectUsage<'a>,
+        Loadout<'a>,
         InitiativeData<'a>,
         ReadStorage<'a, Monster>,
+        ReadStorage<'a, UsesItems>,
         ReadStorage<'a, Coordinate>,
         ReadStorage<'a, Viewshed>,
+        ReadStorage<'a, Durability>,
+        ReadStorage<'a, Item>,
+        ReadStorage<'a, InInventory>,
+        ReadStorage<'a, Usable>,
+        ReadStorage<'a, ProvidesHealing>,
+        Pickups<'a>,
     );
 
     fn run(
@@ -23,10 +72,18 @@ impl<'a> System<'a> for MonsterAISystem {
             entities,
             mut intents,
             mut effect_usage,
+            loadout,
             mut initiative_data,
             monsters,
+            uses_items,
             coordinates,
             viewsheds,
+            durabilities,
+            items,
+            in_inventories,
+            usables,
+            provides_healing,
+            pickups,
         ): Self::SystemData,
     ) {
         let player_coord = *coordinates.get(*player).unwrap();
@@ -43,11 +100,82 @@ impl<'a> System<'a> for MonsterAISystem {
         {
             had_initiative.push(entity);
 
-            if !vs.is_visible(player_coord) {
+            let sees_player = vs.is_visible(player_coord);
+
+            if uses_items.contains(entity) {
+                let carried: SmallVec<[Entity; 4]> = (&entities, &in_inventories)
+                    .join()
+                    .filter(|(_, in_inventory)| in_inventory.owner() == entity)
+                    .map(|(item, _)| item)
+                    .collect();
+
+                // Patch up before it's too late
+                let badly_hurt = durabilities.get(entity).is_some_and(|durability| {
+                    let (health, max_health) = durability.health();
+                    health * 2 <= max_health
+                });
+
+                if badly_hurt
+                    && carried.iter().any(|&item| {
+                        provides_healing.contains(item)
+                            && effect_usage.use_on_self(item, entity).is_ok()
+                    })
+                {
+                    continue;
+                }
+
+                // Grenades are only thrown from outside their own blast, which
+                // goes off early if something is in the way
+                let landing = map.impact(coord, player_coord);
+                if sees_player
+                    && carried.iter().any(|&item| {
+                        blast_radius(usables.get(item)).is_some_and(|radius| {
+                            !map.area_of_effect(landing, radius).contains(&coord)
+                        }) && effect_usage
+                            .use_on_ground(item, entity, player_coord)
+                            .is_ok()
+                    })
+                {
+                    continue;
+                }
+
+                if !sees_player {
+                    let mut items_here = map[coord]
+                        .entities(&items)
+                        .filter(|&item| pickups.can_carry(item, entity))
+                        .peekable();
+                    if items_here.peek().is_some() {
+                        for item in items_here {
+                            intents.wants_to_pick_up(entity, item);
+                        }
+                        continue;
+                    }
+
+                    // Otherwise go for whatever is nearest in sight
+                    let nearest_item = (&entities, &items, &coordinates)
+                        .join()
+                        .filter(|&(item, ..)| pickups.can_carry(item, entity))
+                        .map(|(.., &pos)| pos)
+                        .filter(|&pos| vs.is_visible(pos))
+                        .min_by_key(|&pos| coord.distance(pos));
+
+                    if let Some(dest) = nearest_item
+                        .and_then(|pos| map.path(coord, pos))
+                        .and_then(|mut p| p.nth(1))
+                    {
+                        intents.wants_to_move(entity, dest);
+                    }
+                }
+            }
+
+            if !sees_player {
                 continue;
             }
 
-            match effect_usage.use_on_target(entity, entity, *player) {
+            // Monsters fight bare-handed unless they're carrying a weapon
+            let weapon = loadout.melee_weapon(entity).unwrap_or(entity);
+
+            match effect_usage.use_on_target(weapon, entity, *player) {
                 Ok(()) => {}
                 Err(_) => {
                     if let Some(dest) = map.path(coord, player_coord).and_then(|mut p| p.nth(1)) {
@@ -62,3 +190,45 @@ impl<'a> System<'a> for MonsterAISystem {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::{item, SpawnBuilder},
+        game_mechanics::{give_item, UpgradeLevel},
+    };
+    use test_case::test_case;
+
+    /// An infected crewmember carrying a number of the given item, standing
+    /// next to a nanite cache, a wrench and a repair kit
+    fn crewmember_carrying(item: SpawnBuilder, count: usize) -> (World, Entity, [Entity; 3]) {
+        let mut world = World::new();
+        System::setup(&mut MonsterAISystem, &mut world);
+        world.register::<Description>();
+        world.register::<RecycleValue>();
+        world.register::<UpgradeLevel>();
+        world.register::<DealsDamage>();
+
+        let crewmember = world.create_entity().build();
+        for _ in 0..count {
+            give_item(&mut world, crewmember, item);
+        }
+
+        let cache = item::nanite_cache(world.create_entity(), 5).build();
+        let wrench = item::wrench(world.create_entity()).build();
+        let repair_kit = item::repair_kit(world.create_entity()).build();
+
+        (world, crewmember, [cache, wrench, repair_kit])
+    }
+
+    #[test_case(item::wrench, 0 => [false, true, true]; "empty handed")]
+    #[test_case(item::wrench, INVENTORY_CAPACITY => [false, false, false]; "full")]
+    #[test_case(item::repair_kit, INVENTORY_CAPACITY => [false, false, true]; "full but stacking")]
+    fn only_goes_for_what_it_can_carry(item: SpawnBuilder, count: usize) -> [bool; 3] {
+        let (world, crewmember, nearby) = crewmember_carrying(item, count);
+        let pickups = Pickups::fetch(&world);
+
+        nearby.map(|item| pickups.can_carry(item, crewmember))
+    }
+}
@@ -1,9 +1,10 @@
 use crate::{
-    game_mechanics::{self, HasInitiative},
+    entity::StartingInventory,
+    game_mechanics::{self, HasInitiative, UpgradeLevel},
     level::build_level,
-    player_turn,
+    player_turn::{self, ItemAction},
     prelude::*,
-    targeting::use_ground_effect,
+    targeting::{examine_tile, throw_item, use_ground_effect},
     ui,
 };
 use RunState::*;
@@ -16,6 +17,14 @@ pub enum RunState {
     NewGame,
     AwaitingInput,
     TargetGround(Entity),
+    ChooseItem(ItemAction),
+    ChoosePickup,
+    ThrowItem(Entity),
+    Examine,
+    UseStation(Entity),
+    UseContainer(Entity),
+    CharacterSheet,
+    InventoryScreen,
     Running,
     Quitting,
 }
@@ -38,6 +47,24 @@ impl GameState for GameEngine {
             }
             AwaitingInput => player_turn::handle_input(ctx, &mut self.world),
             TargetGround(effect) => use_ground_effect(effect, ctx, &mut self.world),
+            ChooseItem(action) => ui::item_menu(action, ctx, &mut self.world),
+            ChoosePickup => ui::pickup_menu(ctx, &mut self.world),
+            ThrowItem(item) => throw_item(item, ctx, &mut self.world),
+            Examine => examine_tile(ctx, &mut self.world),
+            UseStation(station) => ui::station_menu(station, ctx, &mut self.world),
+            UseContainer(container) => {
+                match ui::container_menu(container, ctx, &mut self.world) {
+                    // Moving items takes time, but the container stays open
+                    // afterwards unless something needs the player's attention
+                    Running => match self.run() {
+                        AwaitingInput if self.player_is_safe() => UseContainer(container),
+                        run_state => run_state,
+                    },
+                    run_state => run_state,
+                }
+            }
+            CharacterSheet => ui::character_sheet(ctx, &mut self.world),
+            InventoryScreen => ui::inventory_screen(ctx, &mut self.world),
             Running => self.run(),
             Quitting => return ctx.quit(),
         };
@@ -57,6 +84,13 @@ impl GameEngine {
         dispatcher.setup(&mut world);
         ui_dispatcher.setup(&mut world);
         world.register::<Usable>();
+        world.register::<Corpse>();
+        world.register::<Description>();
+        world.register::<Station>();
+        world.register::<RecycleValue>();
+        world.register::<SuitUpgrades>();
+        world.register::<UpgradeLevel>();
+        world.register::<StartingInventory>();
 
         world.insert(RandomNumberGenerator::new());
 
@@ -81,6 +115,24 @@ impl GameEngine {
         AwaitingInput
     }
 
+    /// Alive, with no monsters in sight
+    fn player_is_safe(&self) -> bool {
+        let player = *self.world.fetch::<Entity>();
+        let durabilities = self.world.read_storage::<Durability>();
+        let viewsheds = self.world.read_storage::<Viewshed>();
+        let positions = self.world.read_storage::<Coordinate>();
+        let monsters = self.world.read_storage::<Monster>();
+
+        let alive = durabilities.get(player).is_some_and(Durability::is_alive);
+        let threatened = viewsheds.get(player).is_some_and(|vs| {
+            (&monsters, &positions)
+                .join()
+                .any(|(_, &pos)| vs.is_visible(pos))
+        });
+
+        alive && !threatened
+    }
+
     fn player_has_initiative(&self) -> bool {
         let player = *self.world.fetch::<Entity>();
         let has_initiative = self.world.read_component::<HasInitiative>();
@@ -88,3 +140,130 @@ impl GameEngine {
         has_initiative.contains(player)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::{
+            item,
+            monster::{alien_hatchling, infected_crewmember},
+        },
+        game_mechanics::{give_item, InInventory, InitiativeData, TileEffect},
+        map::DeckBuilder,
+    };
+
+    /// An empty deck with just the player on it
+    fn empty_deck() -> (GameEngine, Entity) {
+        let mut engine = GameEngine::new();
+        let map = DeckBuilder::new(21, 21).spawn(&mut engine.world);
+        let player = {
+            let entities = engine.world.entities();
+            let players = engine.world.read_storage::<Player>();
+
+            (&entities, &players).join().next().unwrap().0
+        };
+
+        engine.world.insert(map);
+        engine.world.insert(player);
+        engine.run();
+
+        (engine, player)
+    }
+
+    fn pass_turn(engine: &mut GameEngine, player: Entity) {
+        InitiativeData::fetch(&engine.world).spend_turn(player);
+        engine.run();
+    }
+
+    #[test]
+    fn hatchling_bites_leave_no_gas() {
+        let (mut engine, player) = empty_deck();
+        let player_pos = *engine
+            .world
+            .read_storage::<Coordinate>()
+            .get(player)
+            .unwrap();
+        alien_hatchling(engine.world.create_entity())
+            .with(player_pos + Direction::North)
+            .build();
+
+        for _ in 0..5 {
+            pass_turn(&mut engine, player);
+        }
+
+        let durabilities = engine.world.read_storage::<Durability>();
+        let (health, max_health) = durabilities.get(player).unwrap().health();
+        assert!(health < max_health, "the hatchling never attacked");
+        assert_eq!(engine.world.read_storage::<TileEffect>().count(), 0);
+    }
+
+    #[test]
+    fn crew_walk_past_nanite_caches() {
+        let (mut engine, player) = empty_deck();
+        let player_pos = *engine
+            .world
+            .read_storage::<Coordinate>()
+            .get(player)
+            .unwrap();
+        let crew_pos = (0..6).fold(player_pos, |pos, _| pos + Direction::North);
+
+        // Short-sighted, so the player stays out of view
+        let crewmember = infected_crewmember(engine.world.create_entity())
+            .with(Viewshed::new(2))
+            .with(crew_pos)
+            .build();
+        let cache = item::nanite_cache(engine.world.create_entity(), 5)
+            .with(crew_pos + Direction::South)
+            .build();
+        let wrench = item::wrench(engine.world.create_entity())
+            .with(crew_pos + Direction::NorthEast + Direction::NorthEast)
+            .build();
+
+        for _ in 0..10 {
+            pass_turn(&mut engine, player);
+        }
+
+        let in_inventories = engine.world.read_storage::<InInventory>();
+        assert_eq!(
+            in_inventories.get(wrench).map(InInventory::owner),
+            Some(crewmember)
+        );
+        assert!(engine.world.is_alive(cache));
+    }
+
+    #[test]
+    fn crew_dont_throw_grenades_into_cover() {
+        let (mut engine, player) = empty_deck();
+        let player_pos = *engine
+            .world
+            .read_storage::<Coordinate>()
+            .get(player)
+            .unwrap();
+        let crew_pos = (0..4).fold(player_pos, |pos, _| pos + Direction::North);
+
+        let crewmember = infected_crewmember(engine.world.create_entity())
+            .with(crew_pos)
+            .build();
+        let grenade = give_item(&mut engine.world, crewmember, item::grenade);
+        // Something right in front of the crewmember would stop the grenade short
+        engine
+            .world
+            .create_entity()
+            .with(BlocksTile)
+            .with(crew_pos + Direction::South)
+            .build();
+
+        // Stop once the crewmember has moved out from behind the cover
+        for _ in 0..5 {
+            pass_turn(&mut engine, player);
+
+            let positions = engine.world.read_storage::<Coordinate>();
+            if positions.get(crewmember) != Some(&crew_pos) {
+                break;
+            }
+        }
+
+        assert!(engine.world.is_alive(grenade));
+    }
+}
@@ -2,11 +2,36 @@ use crate::prelude::*;
 
 pub type SpawnBuilder = fn(EntityBuilder) -> EntityBuilder;
 
-pub const SPAWN_TABLE: [SpawnBuilder; 4] = [
+pub const SPAWN_TABLE: [SpawnBuilder; 9] = [
     monster::infected_crewmember,
     monster::alien_hatchling,
     item::repair_kit,
+    item::medkit,
+    item::welding_torch,
     item::grenade,
+    item::battery,
+    item::sledgehammer,
+    item::pulse_pistol,
+];
+
+/// Gear the player starts every run with
+pub const STARTING_GEAR: [SpawnBuilder; 3] = [item::wrench, item::ev_suit, item::shield_generator];
+
+/// Environmental hazards scattered around each deck
+pub const HAZARDS: [SpawnBuilder; 1] = [tile_effect::electrified_puddle];
+
+/// Storage found around each deck, some of it worth breaking into
+pub const CONTAINERS: [SpawnBuilder; 3] = [
+    container::locker,
+    container::supply_crate,
+    container::supply_cache,
+];
+
+pub const STATIONS: [SpawnBuilder; 4] = [
+    station::repair_station,
+    station::recharge_station,
+    station::recycle_station,
+    station::upgrade_station,
 ];
 
 /// Marker trait for the player entity.
@@ -19,78 +44,600 @@ pub struct Player;
 #[storage(NullStorage)]
 pub struct Monster;
 
+/// Marker trait for monsters clever enough to pick up and use items.
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct UsesItems;
+
 #[derive(Component, PartialEq)]
 pub enum Item {
     Consumable,
+    /// Kept after use, though usually limited by its charges
+    Reusable,
+    Equipment,
+}
+
+/// Marker trait for the remains left behind when something dies.
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct Corpse;
+
+/// Flavor text shown when examining an entity.
+#[derive(Component)]
+pub struct Description(pub String);
+
+/// Rolled when a monster dies to decide what it drops.
+#[derive(Component)]
+pub struct LootTable {
+    pub nanites: DiceType,
+    /// Percent chance for each item to drop
+    pub drops: &'static [(i32, SpawnBuilder)],
 }
 
+/// Rolled when a monster or container spawns to decide what it carries.
+#[derive(Component)]
+pub struct StartingInventory(
+    /// Percent chance for each item to be carried
+    pub &'static [(i32, SpawnBuilder)],
+);
+
 pub fn player(entity: EntityBuilder) -> EntityBuilder {
     entity
         .with(Player)
         .with(Appearance::player())
-        .with(Initiative {
-            current: 1,
-            speed: 6,
-        })
-        .with(Durability::new(30, 2).with_shield(10, 1))
+        .with(Initiative::new(1, 6))
+        .with(Durability::new(10, 0))
         .with(Usable::OnTarget { range: 1 })
-        .with(DealsDamage(5))
+        .with(DealsDamage::new(2))
+        .with(DamageBonus::default())
         .with(Viewshed::new(25))
+        .with(Nanites::default())
+        .with(ModSlots(2))
+        .with(SuitUpgrades::default())
+}
+
+/// Spawns everything the victim's loot table rolls at the given position.
+pub fn drop_loot(
+    loot_table: &LootTable,
+    coord: Coordinate,
+    rng: &mut RandomNumberGenerator,
+    entities: &Entities,
+    lazy: &LazyUpdate,
+) {
+    let nanites = rng.roll(loot_table.nanites);
+    if nanites > 0 {
+        item::nanite_cache(lazy.create_entity(entities), nanites)
+            .with(coord)
+            .build();
+    }
+
+    for &(chance, builder) in loot_table.drops {
+        if rng.range(0, 100) < chance {
+            lazy.exec_mut(move |world| {
+                builder(world.create_entity()).with(coord).build();
+            });
+        }
+    }
+}
+
+pub fn corpse<B: Builder>(entity: B, victim: &Appearance) -> B {
+    entity
+        .with(Corpse)
+        .with(Appearance::corpse(victim))
+        .with(Description(format!("The remains of a dead {victim}.")))
 }
 
 pub mod monster {
-    use crate::prelude::*;
+    use super::{item, tile_effect, LootTable, StartingInventory, UsesItems};
+    use crate::{game_mechanics::DeathEffect, prelude::*};
 
     pub fn infected_crewmember(entity: EntityBuilder) -> EntityBuilder {
         entity
             .with(Monster)
+            .with(UsesItems)
             .with(Appearance::monster("Infected Crewmember", 'z', RED))
-            .with(Initiative {
-                current: 2,
-                speed: 8,
-            })
+            .with(Initiative::new(2, 8))
             .with(Durability::new(16, 1))
             .with(Usable::OnTarget { range: 1 })
-            .with(DealsDamage(4))
+            .with(DealsDamage::new(4))
             .with(Viewshed::new(25))
             .with(BlocksTile)
+            .with(StartingInventory(&[
+                (25, item::wrench),
+                (10, item::sledgehammer),
+                (10, item::repair_kit),
+                (5, item::grenade),
+                (15, item::keycard),
+            ]))
+            .with(LootTable {
+                nanites: DiceType::new(2, 4, 0),
+                drops: &[
+                    (15, item::repair_kit),
+                    (5, item::grenade),
+                    (3, item::emp_grenade),
+                    (3, item::incendiary_grenade),
+                    (3, item::smoke_grenade),
+                    (3, item::cryo_grenade),
+                    (2, item::capacitor_mod),
+                    (2, item::nanite_weave_mod),
+                    (2, item::sensor_mod),
+                    (2, item::servo_mod),
+                    (2, item::quick_release_mod),
+                ],
+            })
     }
 
     pub fn alien_hatchling(entity: EntityBuilder) -> EntityBuilder {
         entity
             .with(Monster)
             .with(Appearance::monster("Alien Hatchling", 'h', RED))
-            .with(Initiative {
-                current: 2,
-                speed: 4,
-            })
+            .with(Initiative::new(2, 4))
             .with(Durability::new(16, 1))
             .with(Usable::OnTarget { range: 1 })
-            .with(DealsDamage(4))
+            .with(DealsDamage::new(4))
             .with(Viewshed::new(25))
             .with(BlocksTile)
+            // Hatchlings burst into a cloud of toxic gas when killed
+            .with(DeathEffect(tile_effect::toxic_gas))
+            .with(LootTable {
+                nanites: DiceType::new(1, 4, 0),
+                drops: &[],
+            })
     }
 }
 
-mod item {
+mod station {
     use crate::prelude::*;
 
+    fn station<'a>(
+        entity: EntityBuilder<'a>,
+        kind: Station,
+        name: &str,
+        color: (u8, u8, u8),
+    ) -> EntityBuilder<'a> {
+        entity
+            .with(kind)
+            .with(Appearance::station(name, color))
+            .with(BlocksTile)
+    }
+
+    pub fn repair_station(entity: EntityBuilder) -> EntityBuilder {
+        station(entity, Station::Repair, "Repair Station", GREEN).with(Description(
+            "Repairs suit durability for a price.".to_string(),
+        ))
+    }
+
+    pub fn recharge_station(entity: EntityBuilder) -> EntityBuilder {
+        station(entity, Station::Recharge, "Recharge Station", BLUE)
+            .with(Description("Recharges shields for a price.".to_string()))
+    }
+
+    pub fn recycle_station(entity: EntityBuilder) -> EntityBuilder {
+        station(entity, Station::Recycle, "Recycle Station", BROWN1).with(Description(
+            "Breaks unwanted items down into nanites.".to_string(),
+        ))
+    }
+
+    pub fn upgrade_station(entity: EntityBuilder) -> EntityBuilder {
+        station(entity, Station::Upgrade, "Upgrade Station", MAGENTA).with(Description(
+            "Permanently improves weapons and suits for a price.".to_string(),
+        ))
+    }
+}
+
+mod container {
+    use super::{item, StartingInventory};
+    use crate::{
+        game_mechanics::{Container, Locked},
+        prelude::*,
+    };
+
+    fn container<'a>(
+        entity: EntityBuilder<'a>,
+        name: &str,
+        glyph: char,
+        color: (u8, u8, u8),
+        turns_to_open: i32,
+    ) -> EntityBuilder<'a> {
+        entity
+            .with(Container::new(turns_to_open))
+            .with(Appearance::container(name, glyph, color))
+            .with(BlocksTile)
+    }
+
+    pub fn locker(entity: EntityBuilder) -> EntityBuilder {
+        container(entity, "Locker", '▐', GREY, 1)
+            .with(Description(
+                "A crew locker. Whoever it belonged to won't be needing it.".to_string(),
+            ))
+            .with(StartingInventory(&[
+                (40, item::repair_kit),
+                (30, item::battery),
+                (20, item::keycard),
+                (15, item::wrench),
+                (10, item::ev_suit),
+            ]))
+    }
+
+    /// Crates are nailed shut and take a while to pry open
+    pub fn supply_crate(entity: EntityBuilder) -> EntityBuilder {
+        container(entity, "Supply Crate", '⌂', BROWN1, 3)
+            .with(Description(
+                "A heavy crate of supplies, sealed for transport.".to_string(),
+            ))
+            .with(StartingInventory(&[
+                (60, item::repair_kit),
+                (40, item::grenade),
+                (40, item::battery),
+                (20, item::emp_grenade),
+                (20, item::smoke_grenade),
+                (10, item::welding_torch),
+            ]))
+    }
+
+    pub fn supply_cache(entity: EntityBuilder) -> EntityBuilder {
+        container(entity, "Supply Cache", 'π', CYAN, 1)
+            .with(Locked)
+            .with(Description(
+                "A security cache, locked tight. It needs a keycard to open.".to_string(),
+            ))
+            .with(StartingInventory(&[
+                (60, item::medkit),
+                (40, item::pulse_pistol),
+                (30, item::sledgehammer),
+                (30, item::shield_generator),
+                (20, item::capacitor_mod),
+                (20, item::servo_mod),
+            ]))
+    }
+}
+
+pub mod item {
+    use super::tile_effect;
+    use crate::{
+        game_mechanics::{Chills, Keycard, LeavesTileEffect, Stack, StripsShields, UpgradeLevel},
+        prelude::*,
+    };
+
+    pub fn nanite_cache<B: Builder>(entity: B, amount: i32) -> B {
+        entity
+            .with(Item::Consumable)
+            .with(Appearance::item(format!("{amount} Nanites"), '$', CYAN))
+            .with(Description(format!(
+                "A cache of {amount} nanites, ready to be put to use."
+            )))
+            .with(NaniteCache(amount))
+    }
+
     pub fn repair_kit(entity: EntityBuilder) -> EntityBuilder {
         entity
             .with(Item::Consumable)
+            .with(Stack::new("repair_kit", repair_kit))
             .with(Appearance::item("Repair Kit", 'δ', ORANGE))
+            .with(Description(
+                "Patches up suit breaches in the field.".to_string(),
+            ))
             .with(Usable::OnSelf)
             .with(ProvidesHealing(8))
+            .with(RecycleValue(4))
+    }
+
+    pub fn medkit(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(Item::Reusable)
+            .with(Appearance::item("Medkit", 'δ', GREEN))
+            .with(Description(
+                "A field medkit with enough supplies for a few repairs.".to_string(),
+            ))
+            .with(Usable::OnSelf)
+            .with(ProvidesHealing(6))
+            .with(Charges::new(3))
+            .with(RecycleValue(8))
+    }
+
+    pub fn welding_torch(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(Item::Reusable)
+            .with(Appearance::item("Welding Torch", '!', YELLOW))
+            .with(Description(
+                "Meant for hull repairs, but just as good at close range.".to_string(),
+            ))
+            .with(Usable::OnTarget { range: 1 })
+            .with(DealsDamage::new(8))
+            .with(Charges::new(4))
+            .with(RecycleValue(8))
     }
 
     pub fn grenade(entity: EntityBuilder) -> EntityBuilder {
         entity
             .with(Item::Consumable)
+            .with(Stack::new("grenade", grenade))
             .with(Appearance::item("Grenade", '*', ORANGE)) // FIXME: better glyph
+            .with(Description(
+                "A fragmentation grenade. Blasts everything nearby away.".to_string(),
+            ))
+            .with(Usable::OnGround {
+                range: 8,
+                radius: 2,
+            })
+            .with(DealsDamage::new(9))
+            .with(Knockback(2))
+            .with(RecycleValue(5))
+    }
+
+    pub fn emp_grenade(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(Item::Consumable)
+            .with(Stack::new("emp_grenade", emp_grenade))
+            .with(Appearance::item("EMP Grenade", '*', CYAN))
+            .with(Description(
+                "Knocks out the shields of everything caught in the pulse.".to_string(),
+            ))
+            .with(Usable::OnGround {
+                range: 8,
+                radius: 2,
+            })
+            .with(StripsShields)
+            .with(RecycleValue(5))
+    }
+
+    pub fn incendiary_grenade(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(Item::Consumable)
+            .with(Stack::new("incendiary_grenade", incendiary_grenade))
+            .with(Appearance::item("Incendiary Grenade", '*', RED))
+            .with(Description("Sets the area ablaze.".to_string()))
+            .with(Usable::OnGround {
+                range: 8,
+                radius: 1,
+            })
+            .with(DealsDamage::new(4))
+            .with(LeavesTileEffect(tile_effect::fire))
+            .with(RecycleValue(5))
+    }
+
+    pub fn smoke_grenade(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(Item::Consumable)
+            .with(Stack::new("smoke_grenade", smoke_grenade))
+            .with(Appearance::item("Smoke Grenade", '*', GRAY))
+            .with(Description(
+                "Fills the area with smoke that blocks line of sight.".to_string(),
+            ))
             .with(Usable::OnGround {
                 range: 8,
                 radius: 2,
             })
-            .with(DealsDamage(9))
+            .with(LeavesTileEffect(tile_effect::smoke))
+            .with(RecycleValue(3))
+    }
+
+    pub fn cryo_grenade(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(Item::Consumable)
+            .with(Stack::new("cryo_grenade", cryo_grenade))
+            .with(Appearance::item("Cryo Grenade", '*', LIGHT_BLUE))
+            .with(Description(
+                "Freezes everything nearby, slowing them down.".to_string(),
+            ))
+            .with(Usable::OnGround {
+                range: 8,
+                radius: 2,
+            })
+            .with(DealsDamage::new(3))
+            .with(Chills(3))
+            .with(RecycleValue(5))
+    }
+
+    pub fn battery(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(Item::Consumable)
+            .with(Stack::new("battery", battery))
+            .with(Appearance::item("Battery", '≡', YELLOW))
+            .with(Description(
+                "Recharges your ranged weapon, or your shield when the weapon is full.".to_string(),
+            ))
+            .with(Usable::OnSelf)
+            .with(RestoresCharge(5))
+            .with(RecycleValue(3))
+    }
+
+    pub fn keycard(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(Item::Consumable)
+            .with(Stack::new("keycard", keycard))
+            .with(Appearance::item("Keycard", '¬', CYAN))
+            .with(Description(
+                "A crew security keycard. Opens one locked cache before the lock wipes it."
+                    .to_string(),
+            ))
+            .with(Keycard)
+            .with(RecycleValue(1))
+    }
+
+    pub fn wrench(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(Item::Equipment)
+            .with(Appearance::item("Wrench", '/', LIGHT_GRAY))
+            .with(Description(
+                "A heavy maintenance wrench. Better than bare hands.".to_string(),
+            ))
+            .with(Equippable(EquipmentSlot::MeleeWeapon))
+            .with(Usable::OnTarget { range: 1 })
+            .with(DealsDamage::new(5))
+            .with(UpgradeLevel::default())
+            .with(RecycleValue(3))
+    }
+
+    pub fn sledgehammer(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(Item::Equipment)
+            .with(Appearance::item("Sledgehammer", '/', ORANGE))
+            .with(Description(
+                "Hits hard enough to knock things back, but swings slowly.".to_string(),
+            ))
+            .with(Equippable(EquipmentSlot::MeleeWeapon))
+            .with(Usable::OnTarget { range: 1 })
+            .with(DealsDamage::new(7))
+            .with(Knockback(1))
+            // Heavy swings leave the wielder open for longer
+            .with(StatModifiers(vec![(
+                StatKind::Speed,
+                Modifier::Percent(25),
+            )]))
+            .with(UpgradeLevel::default())
+            .with(RecycleValue(8))
+    }
+
+    pub fn pulse_pistol(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(Item::Equipment)
+            .with(Appearance::item("Pulse Pistol", '¬', CYAN))
+            .with(Description("A compact energy sidearm.".to_string()))
+            .with(Equippable(EquipmentSlot::RangedWeapon))
+            .with(Usable::OnTarget { range: 6 })
+            .with(DealsDamage::new(6))
+            .with(Charges::new(6))
+            .with(UpgradeLevel::default())
+            .with(RecycleValue(12))
+    }
+
+    pub fn ev_suit(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(Item::Equipment)
+            .with(Appearance::item("EV Suit", '[', LIGHT_GRAY))
+            .with(Description(
+                "Standard issue extravehicular suit.".to_string(),
+            ))
+            .with(Equippable(EquipmentSlot::Suit))
+            .with(StatModifiers(vec![
+                (StatKind::MaxHealth, Modifier::Flat(20)),
+                (StatKind::Defense, Modifier::Flat(2)),
+            ]))
+            .with(RecycleValue(10))
+    }
+
+    fn suit_mod<'a>(
+        entity: EntityBuilder<'a>,
+        name: &str,
+        modifier: (StatKind, Modifier),
+    ) -> EntityBuilder<'a> {
+        entity
+            .with(Item::Equipment)
+            .with(Appearance::item(name, '*', MAGENTA))
+            .with(Description(
+                "Slots into the EV suit to tune its systems.".to_string(),
+            ))
+            .with(Equippable(EquipmentSlot::Mod))
+            .with(StatModifiers(vec![modifier]))
+            .with(RecycleValue(15))
+    }
+
+    pub fn capacitor_mod(entity: EntityBuilder) -> EntityBuilder {
+        suit_mod(
+            entity,
+            "Capacitor Mod",
+            (StatKind::ShieldRegen, Modifier::Flat(3)),
+        )
+    }
+
+    pub fn nanite_weave_mod(entity: EntityBuilder) -> EntityBuilder {
+        suit_mod(
+            entity,
+            "Nanite Weave Mod",
+            (StatKind::HealthRegen, Modifier::Flat(1)),
+        )
+    }
+
+    pub fn sensor_mod(entity: EntityBuilder) -> EntityBuilder {
+        suit_mod(
+            entity,
+            "Sensor Mod",
+            (StatKind::ViewRange, Modifier::Flat(5)),
+        )
+    }
+
+    pub fn servo_mod(entity: EntityBuilder) -> EntityBuilder {
+        suit_mod(
+            entity,
+            "Servo Mod",
+            (StatKind::MeleeDamage, Modifier::Flat(2)),
+        )
+    }
+
+    pub fn quick_release_mod(entity: EntityBuilder) -> EntityBuilder {
+        suit_mod(
+            entity,
+            "Quick-Release Mod",
+            (StatKind::ItemUseTime, Modifier::Percent(-40)),
+        )
+    }
+
+    pub fn shield_generator(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(Item::Equipment)
+            .with(Appearance::item("Shield Generator", '0', BLUE))
+            .with(Description(
+                "Projects a regenerating energy shield around the wearer.".to_string(),
+            ))
+            .with(Equippable(EquipmentSlot::ShieldGenerator))
+            .with(StatModifiers(vec![
+                (StatKind::MaxShield, Modifier::Flat(10)),
+                (StatKind::ShieldDefense, Modifier::Flat(1)),
+                (StatKind::ShieldRegen, Modifier::Flat(5)),
+            ]))
+            .with(RecycleValue(10))
+    }
+}
+
+mod tile_effect {
+    use crate::{
+        game_mechanics::{Spreads, StripsShields, TileEffect},
+        prelude::*,
+    };
+
+    /// Puddles stay put for as long as anyone is likely to be on the deck
+    const PUDDLE_TURNS: i32 = 100;
+
+    pub fn fire(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(TileEffect::new(4))
+            .with(Appearance::tile_effect("Fire", '^', ORANGE))
+            .with(Initiative::new(6, 6))
+            .with(DealsDamage::new(3))
+            .with(Spreads {
+                chance: 15,
+                builder: fire,
+            })
+    }
+
+    pub fn toxic_gas(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(TileEffect::new(5))
+            .with(Appearance::tile_effect("Toxic Gas", '▒', GREEN))
+            .with(Initiative::new(6, 6))
+            .with(DealsDamage::new(2))
+            .with(Spreads {
+                chance: 30,
+                builder: toxic_gas,
+            })
+    }
+
+    /// Shorted wiring makes a long-lasting hazard which also knocks out shields
+    pub fn electrified_puddle(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(TileEffect::new(PUDDLE_TURNS))
+            .with(Appearance::tile_effect("Electrified Puddle", '~', YELLOW))
+            .with(Initiative::new(6, 6))
+            .with(DealsDamage::new(3))
+            .with(StripsShields)
+    }
+
+    pub fn smoke(entity: EntityBuilder) -> EntityBuilder {
+        entity
+            .with(TileEffect::new(8))
+            .with(Appearance::tile_effect("Smoke", '▒', GRAY))
+            .with(Initiative::new(6, 6))
+            .with(BlocksSight)
     }
 }
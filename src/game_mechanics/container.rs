@@ -0,0 +1,104 @@
+use super::{InInventory, Stack};
+use crate::prelude::*;
+
+/// Lockers, crates and supply caches hold items of their own. They take a few
+/// turns of work to get open, but stay open after that.
+#[derive(Component)]
+pub struct Container {
+    /// Turns of work left before it opens
+    work_left: i32,
+}
+
+impl Container {
+    pub fn new(turns_to_open: i32) -> Self {
+        Self {
+            work_left: turns_to_open,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.work_left <= 0
+    }
+}
+
+/// Marker trait for containers which need a keycard before they can be opened.
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct Locked;
+
+/// Marker trait for items which unlock a container, used up in the process.
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct Keycard;
+
+/// Queued on the container, holding whoever is working to open it.
+#[derive(Component)]
+pub struct WantsToOpen(pub(super) Entity);
+
+pub struct OpenContainerSystem;
+
+impl<'a> System<'a> for OpenContainerSystem {
+    type SystemData = (
+        Write<'a, GameLog>,
+        Entities<'a>,
+        WriteStorage<'a, WantsToOpen>,
+        WriteStorage<'a, Container>,
+        WriteStorage<'a, Locked>,
+        ReadStorage<'a, Keycard>,
+        WriteStorage<'a, InInventory>,
+        WriteStorage<'a, Stack>,
+        ReadStorage<'a, Appearance>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut game_log,
+            entities,
+            mut open_intents,
+            mut containers,
+            mut locks,
+            keycards,
+            mut in_inventories,
+            mut stacks,
+            appearances,
+        ): Self::SystemData,
+    ) {
+        for (container, &WantsToOpen(opener), state) in
+            (&entities, &open_intents, &mut containers).join()
+        {
+            let Some(name) = appearances.get(container) else {
+                continue;
+            };
+
+            if locks.contains(container) {
+                let keycard = (&entities, &keycards, &in_inventories)
+                    .join()
+                    .find(|(_, _, carried)| carried.owner() == opener)
+                    .map(|(keycard, ..)| keycard);
+
+                let Some(keycard) = keycard else {
+                    game_log.container_locked(name);
+                    continue;
+                };
+
+                if !stacks.get_mut(keycard).is_some_and(Stack::split) {
+                    in_inventories.remove(keycard);
+                    entities.delete(keycard).unwrap();
+                }
+                locks.remove(container);
+                game_log.container_unlocked(name);
+            }
+
+            state.work_left -= 1;
+
+            if state.is_open() {
+                game_log.container_opened(name);
+            } else {
+                game_log.opening_container(name, state.work_left);
+            }
+        }
+
+        open_intents.clear();
+    }
+}
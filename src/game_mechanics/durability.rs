@@ -1,17 +1,21 @@
-use crate::prelude::*;
+use crate::{
+    entity::{corpse, drop_loot, LootTable},
+    prelude::*,
+};
 
-use super::HasInitiative;
+use super::{drop_spots, DeathEffect, Equipped, HasInitiative, InInventory, Stat, StatKind};
 
 #[derive(Component)]
 pub struct Durability {
     health: i32,
-    max_health: i32,
+    max_health: Stat,
     shield: i32,
-    max_shield: i32,
-    defense: i32,
-    shield_defense: i32,
+    max_shield: Stat,
+    defense: Stat,
+    shield_defense: Stat,
     took_damage: bool,
-    shield_regen: i32,
+    shield_regen: Stat,
+    health_regen: Stat,
     // TODO: broken_shield_cooldown: i32
 }
 
@@ -19,31 +23,38 @@ impl Durability {
     pub fn new(health: i32, defense: i32) -> Self {
         Self {
             health,
-            max_health: health,
+            max_health: Stat::new(health),
             shield: 0,
-            max_shield: 0,
-            defense,
-            shield_defense: 0,
-            shield_regen: 0,
+            max_shield: Stat::new(0),
+            defense: Stat::new(defense),
+            shield_defense: Stat::new(0),
+            shield_regen: Stat::new(0),
+            health_regen: Stat::new(0),
             took_damage: false,
         }
     }
 
-    pub fn with_shield(mut self, shield: i32, shield_defense: i32) -> Self {
-        self.shield = shield;
-        self.max_shield = shield;
-        self.shield_defense = shield_defense;
-        self.shield_regen = 5; // FIXME: don't hardcode
+    pub fn health(&self) -> (i32, i32) {
+        (self.health, self.max_health.value())
+    }
 
-        self
+    pub fn shield(&self) -> Option<(i32, i32)> {
+        let max_shield = self.max_shield.value();
+        (max_shield > 0).then_some((self.shield, max_shield))
     }
 
-    pub fn health(&self) -> (i32, i32) {
-        (self.health, self.max_health)
+    pub fn defense(&self) -> i32 {
+        self.defense.value()
     }
 
-    pub fn shield(&self) -> Option<(i32, i32)> {
-        (self.max_shield > 0).then(|| (self.shield, self.max_shield))
+    /// Shield defense and regeneration per turn
+    pub fn shield_stats(&self) -> (i32, i32) {
+        (self.shield_defense.value(), self.shield_regen.value())
+    }
+
+    /// Durability repaired per turn
+    pub fn health_regen(&self) -> i32 {
+        self.health_regen.value()
     }
 
     pub fn is_alive(&self) -> bool {
@@ -52,49 +63,127 @@ impl Durability {
 
     /// Returns the amount actually healed
     pub fn heal(&mut self, healing: i32) -> i32 {
-        let healing = i32::min(healing, self.max_health - self.health);
+        let healing = i32::min(healing, self.max_health.value() - self.health);
         self.health += healing;
 
         healing
     }
 
+    /// Returns the amount actually recharged
+    pub fn recharge_shield(&mut self, amount: i32) -> i32 {
+        let amount = i32::min(amount, self.max_shield.value() - self.shield);
+        self.shield += amount;
+
+        amount
+    }
+
+    /// Permanently raises max health, healing by the same amount
+    pub fn reinforce(&mut self, amount: i32) {
+        self.max_health.increase_base(amount);
+        self.health += amount;
+    }
+
+    /// Knocks out the shield entirely. Returns the amount of shield lost.
+    pub fn strip_shield(&mut self) -> i32 {
+        self.took_damage = true;
+
+        std::mem::take(&mut self.shield)
+    }
+
+    /// Changes one of the durability stats.
+    ///
+    /// Health and shield keep the same fraction of their maximum, so swapping
+    /// gear can't be used to repair or recharge for free.
+    pub fn modify(&mut self, kind: StatKind, f: impl FnOnce(&mut Stat)) {
+        let (max_health, max_shield) = (self.max_health.value(), self.max_shield.value());
+
+        match kind {
+            StatKind::MaxHealth => f(&mut self.max_health),
+            StatKind::Defense => f(&mut self.defense),
+            StatKind::MaxShield => f(&mut self.max_shield),
+            StatKind::ShieldDefense => f(&mut self.shield_defense),
+            StatKind::ShieldRegen => f(&mut self.shield_regen),
+            StatKind::HealthRegen => f(&mut self.health_regen),
+            _ => {}
+        }
+
+        let new_max_health = self.max_health.value();
+        if new_max_health != max_health {
+            // Anything still standing keeps at least 1 health, but anything
+            // destroyed stays destroyed
+            self.health = if max_health <= 0 {
+                new_max_health
+            } else if self.health <= 0 {
+                0
+            } else {
+                i32::max(1, self.health * new_max_health / max_health)
+            };
+        }
+
+        let new_max_shield = self.max_shield.value();
+        if new_max_shield != max_shield {
+            self.shield = if max_shield > 0 {
+                self.shield * new_max_shield / max_shield
+            } else {
+                0
+            };
+        }
+    }
+
     /// Returns the amount of damage actually taken
     pub fn take_damage(&mut self, damage: i32) -> i32 {
         self.took_damage = true;
 
+        let (damage_to_shield, damage_to_health) = self.split_damage(damage);
+        self.shield -= damage_to_shield;
+        self.health -= damage_to_health;
+
+        damage_to_shield + damage_to_health
+    }
+
+    /// The damage that would actually be taken, without taking it
+    pub fn expected_damage(&self, damage: i32) -> i32 {
+        let (damage_to_shield, damage_to_health) = self.split_damage(damage);
+
+        damage_to_shield + damage_to_health
+    }
+
+    /// How much damage gets through to the shield and to health after defenses
+    fn split_damage(&self, damage: i32) -> (i32, i32) {
         let (damage_to_shield, unshielded_damage) = if self.shield > 0 {
-            let blocked_damage = i32::max(0, damage - self.shield_defense);
+            let blocked_damage = i32::max(0, damage - self.shield_defense.value());
             let damage_to_shield = i32::min(blocked_damage, self.shield);
-            self.shield -= damage_to_shield;
 
             (damage_to_shield, blocked_damage - damage_to_shield)
         } else {
             (0, damage)
         };
 
-        let damage_to_health = i32::clamp(unshielded_damage - self.defense, 0, self.health);
-        self.health -= damage_to_health;
+        let damage_to_health = i32::clamp(unshielded_damage - self.defense.value(), 0, self.health);
 
-        damage_to_shield + damage_to_health
+        (damage_to_shield, damage_to_health)
     }
 }
 
-pub struct ShieldRegenSystem;
+/// Slowly repairs and recharges everyone whose turn it is. Shields only
+/// recharge when they haven't been hit since the last turn.
+pub struct RegenSystem;
 
-impl<'a> System<'a> for ShieldRegenSystem {
+impl<'a> System<'a> for RegenSystem {
     type SystemData = (WriteStorage<'a, Durability>, ReadStorage<'a, HasInitiative>);
 
     fn run(&mut self, (mut durabilities, has_initiative): Self::SystemData) {
         for (durability, _) in (&mut durabilities, &has_initiative).join() {
+            if durability.is_alive() {
+                durability.heal(durability.health_regen.value());
+            }
+
             if durability.took_damage {
                 durability.took_damage = false;
                 continue;
             }
 
-            durability.shield = i32::min(
-                durability.shield + durability.shield_regen,
-                durability.max_shield,
-            );
+            durability.recharge_shield(durability.shield_regen.value());
         }
     }
 }
@@ -103,31 +192,82 @@ pub struct DeathSystem;
 
 impl<'a> System<'a> for DeathSystem {
     type SystemData = (
+        ReadExpect<'a, Map>,
         Entities<'a>,
         ReadStorage<'a, Player>,
         ReadStorage<'a, Appearance>,
+        ReadStorage<'a, LootTable>,
+        ReadStorage<'a, DeathEffect>,
+        ReadStorage<'a, Nanites>,
+        ReadStorage<'a, Item>,
         WriteStorage<'a, Durability>,
         WriteStorage<'a, Coordinate>,
+        WriteStorage<'a, InInventory>,
+        WriteStorage<'a, Equipped>,
         Write<'a, GameLog>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        Read<'a, LazyUpdate>,
     );
 
     fn run(
         &mut self,
-        (entities, players, names, mut durabilities, mut positions, mut game_log): Self::SystemData,
+        (
+            map,
+            entities,
+            players,
+            names,
+            loot_tables,
+            death_effects,
+            nanites,
+            items,
+            mut durabilities,
+            mut positions,
+            mut in_inventories,
+            mut equipped,
+            mut game_log,
+            mut rng,
+            lazy,
+        ): Self::SystemData,
     ) {
         for (entity, appearance, durability) in (&entities, &names, &mut durabilities).join() {
             if !durability.is_alive() {
                 if players.contains(entity) {
                     // TODO: handle player death
-                    game_log.player_death();
-                    durability.health = durability.max_health;
+                    game_log.player_death(nanites.get(entity));
+                    durability.health = durability.max_health.value();
                 } else {
                     game_log.death(appearance);
 
                     // Removing the position clears the entity off the map immediately.
                     // All other components will be removed automatically after the turn.
-                    positions.remove(entity).unwrap();
+                    let coord = positions.remove(entity).unwrap();
                     entities.delete(entity).unwrap();
+
+                    let remains = corpse(lazy.create_entity(&entities), appearance);
+                    remains.with(coord).build();
+
+                    // Everything they carried falls around them
+                    let carried: Vec<_> = (&entities, &in_inventories)
+                        .join()
+                        .filter(|&(_, &InInventory(owner))| owner == entity)
+                        .map(|(item, _)| item)
+                        .collect();
+
+                    for (item, spot) in carried.into_iter().zip(drop_spots(&map, coord, &items)) {
+                        in_inventories.remove(item);
+                        equipped.remove(item);
+                        positions.insert(item, spot).unwrap();
+                    }
+
+                    if let Some(&DeathEffect(builder)) = death_effects.get(entity) {
+                        lazy.exec_mut(move |world| {
+                            builder(world.create_entity()).with(coord).build();
+                        });
+                    }
+
+                    if let Some(loot_table) = loot_tables.get(entity) {
+                        drop_loot(loot_table, coord, &mut rng, &entities, &lazy);
+                    }
                 }
             }
         }
@@ -136,16 +276,17 @@ impl<'a> System<'a> for DeathSystem {
 
 #[cfg(test)]
 mod test {
-    use {super::*, test_case::test_case};
+    use {super::*, crate::game_mechanics::Modifier, test_case::test_case};
 
     const EXAMPLE_DURABILITY: Durability = Durability {
         health: 30,
-        max_health: 30,
+        max_health: Stat::new(30),
         shield: 10,
-        max_shield: 10,
-        defense: 2,
-        shield_defense: 1,
-        shield_regen: 5,
+        max_shield: Stat::new(10),
+        defense: Stat::new(2),
+        shield_defense: Stat::new(1),
+        shield_regen: Stat::new(5),
+        health_regen: Stat::new(0),
         took_damage: false,
     };
 
@@ -186,12 +327,68 @@ mod test {
         )
     }
 
+    #[test_case(2)]
+    #[test_case(6)]
+    #[test_case(99)]
+    fn expected_damage_matches_damage_taken(damage: i32) {
+        let mut durability = Durability {
+            shield: 2,
+            ..EXAMPLE_DURABILITY
+        };
+
+        let expected = durability.expected_damage(damage);
+
+        assert_eq!(durability.take_damage(damage), expected);
+    }
+
+    #[test_case(30 => (10, 10); "undamaged")]
+    #[test_case(15 => (5, 10); "half health")]
+    #[test_case(1 => (1, 10); "nearly destroyed")]
+    #[test_case(0 => (0, 10); "destroyed")]
+    fn removing_modifiers_keeps_fraction_of_health(health: i32) -> (i32, i32) {
+        let suit = World::new().create_entity().build();
+        let mut max_health = Stat::new(10);
+        max_health.add_modifier(suit, Modifier::Flat(20));
+        let mut durability = Durability {
+            health,
+            max_health,
+            ..EXAMPLE_DURABILITY
+        };
+
+        durability.modify(StatKind::MaxHealth, |s| s.remove_modifiers(suit));
+
+        durability.health()
+    }
+
+    #[test]
+    fn swapping_gear_does_not_heal() {
+        let mut world = World::new();
+        let (suit, generator) = (world.create_entity().build(), world.create_entity().build());
+        let mut durability = Durability {
+            health: 15,
+            shield: 4,
+            ..EXAMPLE_DURABILITY
+        };
+
+        durability.modify(StatKind::MaxHealth, |s| {
+            s.add_modifier(suit, Modifier::Flat(10))
+        });
+        durability.modify(StatKind::MaxShield, |s| {
+            s.add_modifier(generator, Modifier::Percent(100))
+        });
+        durability.modify(StatKind::MaxHealth, |s| s.remove_modifiers(suit));
+        durability.modify(StatKind::MaxShield, |s| s.remove_modifiers(generator));
+
+        assert_eq!(durability.health(), (15, 30));
+        assert_eq!(durability.shield(), Some((4, 10)));
+    }
+
     #[test_case(0, 0; "no shield")]
     #[test_case(0, 10; "broken shield")]
     fn shield_defense_doesnt_apply_when_shield_is_down(shield: i32, max_shield: i32) {
         let mut durability = Durability {
             shield,
-            max_shield,
+            max_shield: Stat::new(max_shield),
             ..EXAMPLE_DURABILITY
         };
 
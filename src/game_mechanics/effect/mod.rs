@@ -2,7 +2,10 @@ mod usage;
 
 pub use usage::EffectUsage;
 
-use super::InInventory;
+use super::{
+    DamageBonus, HasInitiative, InInventory, Knockback, LeavesTileEffect, Loadout, Pushed, Stack,
+    Stat, StatData,
+};
 use crate::prelude::*;
 
 #[derive(Component, Clone, Copy)]
@@ -14,57 +17,194 @@ pub enum Usable {
 }
 
 #[derive(Component)]
-pub struct BeingUsed(pub(super) SmallVec<[Entity; 1]>);
+pub struct BeingUsed {
+    pub(super) targets: SmallVec<[Entity; 1]>,
+    /// Where the effect originates from, e.g. the attacker or the center of a blast.
+    pub(super) origin: Coordinate,
+    pub(super) user: Entity,
+}
 
 #[derive(Component)]
-pub struct DealsDamage(pub i32);
+pub struct DealsDamage(pub Stat);
+
+impl DealsDamage {
+    pub fn new(damage: i32) -> Self {
+        Self(Stat::new(damage))
+    }
+}
 
 #[derive(Component)]
 pub struct ProvidesHealing(pub i32);
 
+/// Restores charges to the target's ranged weapon, then to anything else they
+/// carry that uses charges, and finally to their shield.
+#[derive(Component)]
+pub struct RestoresCharge(pub i32);
+
+/// Knocks out the target's shield, e.g. with an EMP
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct StripsShields;
+
+/// Slows the target down for this many of its turns
+#[derive(Component)]
+pub struct Chills(pub i32);
+
+/// Added to the turn delay of anything chilled
+const CHILL_SLOWDOWN: Modifier = Modifier::Percent(50);
+
+/// Slowed down for a few turns, e.g. by a cryo grenade
+#[derive(Component)]
+pub struct Chilled {
+    turns_left: i32,
+    /// Whether the slowdown has been applied to the entity's stats yet
+    applied: bool,
+}
+
+/// Limited uses before needing to be recharged
+#[derive(Component)]
+pub struct Charges {
+    current: i32,
+    max: i32,
+}
+
+impl Charges {
+    pub fn new(max: i32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn current(&self) -> i32 {
+        self.current
+    }
+
+    pub fn max(&self) -> i32 {
+        self.max
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.current <= 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.current >= self.max
+    }
+
+    /// Raises the capacity, filling the new charges
+    pub fn increase_max(&mut self, amount: i32) {
+        self.max += amount;
+        self.current += amount;
+    }
+
+    /// Returns the number of charges actually restored
+    pub fn recharge(&mut self, amount: i32) -> i32 {
+        let amount = i32::min(amount, self.max - self.current);
+        self.current += amount;
+
+        amount
+    }
+
+    fn spend(&mut self) {
+        self.current = i32::max(0, self.current - 1);
+    }
+}
+
 pub struct EffectUseSystem;
 
 impl<'a> System<'a> for EffectUseSystem {
     type SystemData = (
+        ReadExpect<'a, Map>,
         Entities<'a>,
         ReadStorage<'a, Item>,
+        ReadStorage<'a, Usable>,
         WriteStorage<'a, Durability>,
         WriteStorage<'a, BeingUsed>,
         ReadStorage<'a, DealsDamage>,
+        ReadStorage<'a, DamageBonus>,
         ReadStorage<'a, ProvidesHealing>,
+        ReadStorage<'a, RestoresCharge>,
+        ReadStorage<'a, StripsShields>,
+        ReadStorage<'a, Chills>,
+        ReadStorage<'a, LeavesTileEffect>,
+        WriteStorage<'a, Chilled>,
+        WriteStorage<'a, Charges>,
+        Loadout<'a>,
+        ReadStorage<'a, Knockback>,
+        WriteStorage<'a, Pushed>,
         WriteStorage<'a, InInventory>,
+        WriteStorage<'a, Stack>,
         ReadStorage<'a, Appearance>,
         Write<'a, GameLog>,
+        Read<'a, LazyUpdate>,
     );
 
     fn run(
         &mut self,
         (
+            map,
             entities,
             item_types,
+            usables,
             mut durabilities,
             mut item_use_intents,
             deals_damage,
+            damage_bonuses,
             provides_healing,
+            restores_charge,
+            strips_shields,
+            chills,
+            leaves_tile_effects,
+            mut chilled,
+            mut charges,
+            loadout,
+            knockbacks,
+            mut pushes,
             mut in_inventories,
+            mut stacks,
             names,
             mut game_log,
+            lazy,
         ): Self::SystemData,
     ) {
-        for (item, item_type, BeingUsed(targets), item_name, damage, healing) in (
+        for (
+            item,
+            item_type,
+            BeingUsed {
+                targets,
+                origin,
+                user,
+            },
+            item_name,
+            damage,
+            healing,
+            recharge,
+            knockback,
+        ) in (
             &entities,
             item_types.maybe(),
             &item_use_intents,
             &names,
             deals_damage.maybe(),
             provides_healing.maybe(),
+            restores_charge.maybe(),
+            knockbacks.maybe(),
         )
             .join()
         {
+            // Weapons hit harder in the hands of an upgraded suit. Attacking
+            // without a weapon counts as melee.
+            let bonus = damage_bonuses
+                .get(*user)
+                .map_or(0, |bonus| match loadout.slot(item) {
+                    Some(EquipmentSlot::MeleeWeapon) => bonus.melee.value(),
+                    Some(EquipmentSlot::RangedWeapon) => bonus.ranged.value(),
+                    None if item == *user => bonus.melee.value(),
+                    _ => 0,
+                });
+
             for &target in targets {
                 if let Some(durability) = durabilities.get_mut(target) {
-                    if let Some(&DealsDamage(raw_damage)) = damage {
-                        let blocked_damage = durability.take_damage(raw_damage);
+                    if let Some(DealsDamage(raw_damage)) = damage {
+                        let blocked_damage = durability.take_damage(raw_damage.value() + bonus);
                         if let Some(target_name) = names.get(target) {
                             game_log.damage(item_name, target_name, blocked_damage);
                         }
@@ -76,14 +216,96 @@ impl<'a> System<'a> for EffectUseSystem {
                             game_log.healing(item_name, target_name, amount);
                         }
                     }
+
+                    if let Some(&RestoresCharge(amount)) = recharge {
+                        let needs_charge =
+                            |item| charges.get(item).is_some_and(|c: &Charges| !c.is_full());
+                        let weapon = loadout
+                            .in_slot(target, EquipmentSlot::RangedWeapon)
+                            .find(|&weapon| needs_charge(weapon))
+                            .or_else(|| {
+                                (&entities, &in_inventories)
+                                    .join()
+                                    .find(|&(carried, &InInventory(owner))| {
+                                        owner == target && needs_charge(carried)
+                                    })
+                                    .map(|(carried, _)| carried)
+                            });
+
+                        if let Some(weapon) = weapon {
+                            let amount = charges.get_mut(weapon).unwrap().recharge(amount);
+                            if let Some(weapon_name) = names.get(weapon) {
+                                game_log.weapon_recharged(item_name, weapon_name, amount);
+                            }
+                        } else {
+                            let amount = durability.recharge_shield(amount);
+                            if let Some(target_name) = names.get(target) {
+                                game_log.shield_recharged(item_name, target_name, amount);
+                            }
+                        }
+                    }
+
+                    if strips_shields.contains(item) {
+                        let amount = durability.strip_shield();
+                        if let Some(target_name) = names.get(target) {
+                            game_log.shield_stripped(item_name, target_name, amount);
+                        }
+                    }
+
+                    // Chilling something again only makes it last longer
+                    if let Some(&Chills(turns)) = chills.get(item) {
+                        match chilled.get_mut(target) {
+                            Some(chill) => chill.turns_left = chill.turns_left.max(turns),
+                            None => {
+                                let chill = Chilled {
+                                    turns_left: turns,
+                                    applied: false,
+                                };
+                                chilled.insert(target, chill).unwrap();
+                            }
+                        }
+
+                        if let Some(target_name) = names.get(target) {
+                            game_log.chilled(item_name, target_name);
+                        }
+                    }
+
+                    if let Some(&Knockback(distance)) = knockback {
+                        let push = Pushed {
+                            origin: *origin,
+                            distance,
+                        };
+                        pushes.insert(target, push).unwrap();
+                    }
                 }
             }
 
-            if item_type == Some(&Item::Consumable) {
+            if let Some(&LeavesTileEffect(builder)) = leaves_tile_effects.get(item) {
+                let radius = match usables.get(item) {
+                    Some(&Usable::OnGround { radius, .. }) => radius,
+                    _ => 0,
+                };
+
+                for pos in map.area_of_effect(*origin, radius) {
+                    lazy.exec_mut(move |world| {
+                        builder(world.create_entity()).with(pos).build();
+                    });
+                }
+            }
+
+            if let Some(charges) = charges.get_mut(item) {
+                charges.spend();
+            }
+
+            // Stacked consumables are used up one at a time
+            let stacked = stacks.get_mut(item).is_some_and(Stack::split);
+
+            if item_type == Some(&Item::Consumable) && !stacked {
                 // Removing the inventory marker clears the entity from the player's
                 // inventory immediately. All other components will be removed
-                // automatically after the turn.
-                in_inventories.remove(item).unwrap();
+                // automatically after the turn. Thrown items have already left
+                // the inventory.
+                in_inventories.remove(item);
                 entities.delete(item).unwrap();
             }
         }
@@ -91,3 +313,42 @@ impl<'a> System<'a> for EffectUseSystem {
         item_use_intents.clear();
     }
 }
+
+/// Slows down anything chilled until it has taken enough turns to thaw out.
+///
+/// A chilled entity is the source of its own slowdown modifier, so thawing
+/// out leaves any gear's modifiers alone.
+pub struct ChillSystem;
+
+impl<'a> System<'a> for ChillSystem {
+    type SystemData = (
+        Entities<'a>,
+        WriteStorage<'a, Chilled>,
+        ReadStorage<'a, HasInitiative>,
+        StatData<'a>,
+    );
+
+    fn run(&mut self, (entities, mut chilled, has_initiative, mut stats): Self::SystemData) {
+        let slowdown = StatModifiers(vec![(StatKind::Speed, CHILL_SLOWDOWN)]);
+        let mut thawed = Vec::new();
+
+        for (entity, chill) in (&entities, &mut chilled).join() {
+            if !std::mem::replace(&mut chill.applied, true) {
+                stats.apply(entity, entity, &slowdown);
+            }
+
+            // Turns are counted as they start
+            if has_initiative.contains(entity) {
+                if chill.turns_left <= 0 {
+                    thawed.push(entity);
+                }
+                chill.turns_left -= 1;
+            }
+        }
+
+        for entity in thawed {
+            stats.remove(entity, entity);
+            chilled.remove(entity);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::super::BeingUsed;
+use super::super::{BeingUsed, Charges};
 use crate::prelude::*;
 use anyhow::{bail, ensure, Context, Result};
 
@@ -6,7 +6,10 @@ use anyhow::{bail, ensure, Context, Result};
 pub struct EffectUsage<'a> {
     map: ReadExpect<'a, Map>,
     usables: ReadStorage<'a, Usable>,
+    charges: ReadStorage<'a, Charges>,
+    appearances: ReadStorage<'a, Appearance>,
     positions: ReadStorage<'a, Coordinate>,
+    blockers: ReadStorage<'a, BlocksTile>,
     being_used: WriteStorage<'a, BeingUsed>,
 }
 
@@ -16,8 +19,18 @@ impl<'a> EffectUsage<'a> {
             Some(&Usable::OnSelf) => {}
             _ => bail!("not usable on self"),
         };
+        self.ensure_charged(effect)?;
 
-        self.being_used.insert(effect, BeingUsed(smallvec![user]))?;
+        let user_pos = *self.positions.get(user).context("invalid user")?;
+
+        self.being_used.insert(
+            effect,
+            BeingUsed {
+                targets: smallvec![user],
+                origin: user_pos,
+                user,
+            },
+        )?;
 
         Ok(())
     }
@@ -27,6 +40,7 @@ impl<'a> EffectUsage<'a> {
             Some(&Usable::OnTarget { range }) => range,
             _ => bail!("not usable on target"),
         };
+        self.ensure_charged(effect)?;
 
         let user_pos = *self.positions.get(user).context("invalid user")?;
         let target_pos = *self.positions.get(target).context("invalid target")?;
@@ -36,8 +50,28 @@ impl<'a> EffectUsage<'a> {
             "target out of range"
         );
 
-        self.being_used
-            .insert(effect, BeingUsed(smallvec![target]))?;
+        // Anything else standing in the way takes the hit instead.
+        let impact = *self
+            .map
+            .line_of_fire(user_pos, target_pos)
+            .last()
+            .context("no line of fire")?;
+        let target = if impact == target_pos {
+            target
+        } else {
+            self.map[impact]
+                .entity(&self.blockers)
+                .context("no line of fire")?
+        };
+
+        self.being_used.insert(
+            effect,
+            BeingUsed {
+                targets: smallvec![target],
+                origin: user_pos,
+                user,
+            },
+        )?;
 
         Ok(())
     }
@@ -52,6 +86,7 @@ impl<'a> EffectUsage<'a> {
             Some(&Usable::OnGround { range, radius }) => (range, radius),
             _ => bail!("not usable on ground"),
         };
+        self.ensure_charged(effect)?;
 
         let user_pos = *self.positions.get(user).context("invalid user")?;
         ensure!(
@@ -59,6 +94,8 @@ impl<'a> EffectUsage<'a> {
             "target out of range"
         );
 
+        let target_pos = self.map.impact(user_pos, target_pos);
+
         let targets = self
             .map
             .area_of_effect(target_pos, radius)
@@ -66,7 +103,25 @@ impl<'a> EffectUsage<'a> {
             .flat_map(|pos| self.map[pos].iter())
             .collect();
 
-        self.being_used.insert(effect, BeingUsed(targets))?;
+        self.being_used.insert(
+            effect,
+            BeingUsed {
+                targets,
+                origin: target_pos,
+                user,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn ensure_charged(&self, effect: Entity) -> Result<()> {
+        if self.charges.get(effect).is_some_and(Charges::is_empty) {
+            match self.appearances.get(effect) {
+                Some(name) => bail!("{name} is out of charges"),
+                None => bail!("out of charges"),
+            }
+        }
 
         Ok(())
     }
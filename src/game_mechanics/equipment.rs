@@ -0,0 +1,196 @@
+use super::{give_item, StatData};
+use crate::{entity::SpawnBuilder, prelude::*};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EquipmentSlot {
+    MeleeWeapon,
+    RangedWeapon,
+    Suit,
+    ShieldGenerator,
+    Mod,
+}
+
+impl EquipmentSlot {
+    pub const ALL: [EquipmentSlot; 5] = [
+        Self::MeleeWeapon,
+        Self::RangedWeapon,
+        Self::Suit,
+        Self::ShieldGenerator,
+        Self::Mod,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MeleeWeapon => "Melee",
+            Self::RangedWeapon => "Ranged",
+            Self::Suit => "Suit",
+            Self::ShieldGenerator => "Shield",
+            Self::Mod => "Mods",
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Equippable(pub EquipmentSlot);
+
+/// Marks an item as equipped by its owner.
+#[derive(Component)]
+pub struct Equipped(pub(super) Entity);
+
+/// How many mods an entity can have equipped at once
+#[derive(Component)]
+pub struct ModSlots(pub usize);
+
+/// Queued on the item rather than the wearer, so that several pieces of gear
+/// can be equipped at once.
+#[derive(Component)]
+pub struct WantsToEquip(pub(super) Entity);
+
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct WantsToUnequip;
+
+#[derive(SystemData)]
+pub struct Loadout<'a> {
+    entities: Entities<'a>,
+    equipped: ReadStorage<'a, Equipped>,
+    equippables: ReadStorage<'a, Equippable>,
+}
+
+impl<'a> Loadout<'a> {
+    pub fn is_equipped(&self, item: Entity) -> bool {
+        self.equipped.contains(item)
+    }
+
+    pub fn is_equippable(&self, item: Entity) -> bool {
+        self.equippables.contains(item)
+    }
+
+    /// Everything the owner has equipped in the given slot
+    pub fn in_slot(&self, owner: Entity, slot: EquipmentSlot) -> impl Iterator<Item = Entity> + '_ {
+        (&self.entities, &self.equipped, &self.equippables)
+            .join()
+            .filter(move |&(_, &Equipped(o), &Equippable(s))| o == owner && s == slot)
+            .map(|(item, _, _)| item)
+    }
+
+    pub fn slot(&self, item: Entity) -> Option<EquipmentSlot> {
+        self.equippables.get(item).map(|&Equippable(slot)| slot)
+    }
+
+    pub fn melee_weapon(&self, owner: Entity) -> Option<Entity> {
+        self.in_slot(owner, EquipmentSlot::MeleeWeapon).next()
+    }
+
+    pub fn ranged_weapon(&self, owner: Entity) -> Option<Entity> {
+        self.in_slot(owner, EquipmentSlot::RangedWeapon).next()
+    }
+}
+
+/// Spawns an item straight into the owner's inventory and equips it.
+pub fn give_equipped(world: &mut World, owner: Entity, builder: SpawnBuilder) {
+    let item = give_item(world, owner, builder);
+    world
+        .write_storage::<WantsToEquip>()
+        .insert(item, WantsToEquip(owner))
+        .unwrap();
+}
+
+pub struct EquipSystem;
+
+impl<'a> System<'a> for EquipSystem {
+    type SystemData = (
+        ReadExpect<'a, Entity>,
+        Write<'a, GameLog>,
+        Entities<'a>,
+        WriteStorage<'a, WantsToEquip>,
+        WriteStorage<'a, WantsToUnequip>,
+        WriteStorage<'a, Equipped>,
+        ReadStorage<'a, Equippable>,
+        ReadStorage<'a, ModSlots>,
+        ReadStorage<'a, StatModifiers>,
+        StatData<'a>,
+        ReadStorage<'a, Appearance>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            player,
+            mut game_log,
+            entities,
+            mut equip_intents,
+            mut unequip_intents,
+            mut equipped,
+            equippables,
+            mod_slots,
+            stat_modifiers,
+            mut stats,
+            appearances,
+        ): Self::SystemData,
+    ) {
+        // Equipping replaces whatever was already in that slot, except for mods
+        // which have several slots of their own.
+        let replaced = (&equip_intents, &equippables)
+            .join()
+            .filter(|(_, &Equippable(slot))| slot != EquipmentSlot::Mod)
+            .flat_map(|(&WantsToEquip(owner), &Equippable(slot))| {
+                (&entities, &equipped, &equippables)
+                    .join()
+                    .filter(move |&(_, &Equipped(o), &Equippable(s))| o == owner && s == slot)
+                    .map(|(item, _, _)| item)
+            });
+
+        let to_unequip: Vec<Entity> = (&entities, &unequip_intents)
+            .join()
+            .map(|(item, _)| item)
+            .chain(replaced)
+            .collect();
+
+        for item in to_unequip {
+            if let Some(Equipped(owner)) = equipped.remove(item) {
+                stats.remove(owner, item);
+
+                if owner == *player {
+                    if let Some(appearance) = appearances.get(item) {
+                        game_log.unequip(appearance);
+                    }
+                }
+            }
+        }
+
+        for (item, &WantsToEquip(owner), &Equippable(slot)) in
+            (&entities, &equip_intents, &equippables).join()
+        {
+            if slot == EquipmentSlot::Mod {
+                let available = mod_slots.get(owner).map_or(0, |&ModSlots(n)| n);
+                let mods_equipped = (&equipped, &equippables)
+                    .join()
+                    .filter(|&(&Equipped(o), &Equippable(s))| o == owner && s == slot)
+                    .count();
+
+                if mods_equipped >= available {
+                    if owner == *player {
+                        game_log.no_free_mod_slot();
+                    }
+                    continue;
+                }
+            }
+
+            equipped.insert(item, Equipped(owner)).unwrap();
+
+            if let Some(modifiers) = stat_modifiers.get(item) {
+                stats.apply(owner, item, modifiers);
+            }
+
+            if owner == *player {
+                if let Some(appearance) = appearances.get(item) {
+                    game_log.equip(appearance);
+                }
+            }
+        }
+
+        equip_intents.clear();
+        unequip_intents.clear();
+    }
+}
@@ -4,33 +4,49 @@ use std::collections::HashSet;
 #[derive(Component)]
 pub struct Viewshed {
     visible_tiles: HashSet<Coordinate>,
-    range: i32,
+    range: Stat,
+    /// Set when the range changes so the field of view gets recalculated
+    dirty: bool,
 }
 
 impl Viewshed {
     pub fn new(range: i32) -> Self {
         Self {
             visible_tiles: HashSet::new(),
-            range,
+            range: Stat::new(range),
+            dirty: false,
         }
     }
 
     pub fn is_visible(&self, coord: Coordinate) -> bool {
         self.visible_tiles.contains(&coord)
     }
+
+    pub fn range(&self) -> i32 {
+        self.range.value()
+    }
+
+    pub fn modify_range(&mut self, f: impl FnOnce(&mut Stat)) {
+        f(&mut self.range);
+        self.dirty = true;
+    }
 }
 
 pub struct VisibilitySystem {
     cursor: ReaderId<ComponentEvent>,
+    sight_blocker_cursor: ReaderId<ComponentEvent>,
 }
 
 impl VisibilitySystem {
     pub fn new(world: &mut World) -> Self {
         world.register::<Coordinate>();
+        world.register::<BlocksSight>();
         let mut coordinates = world.write_component::<Coordinate>();
+        let mut sight_blockers = world.write_component::<BlocksSight>();
 
         Self {
             cursor: coordinates.register_reader(),
+            sight_blocker_cursor: sight_blockers.register_reader(),
         }
     }
 }
@@ -41,13 +57,17 @@ impl<'a> System<'a> for VisibilitySystem {
         WriteExpect<'a, Map>,
         Entities<'a>,
         ReadStorage<'a, Coordinate>,
+        ReadStorage<'a, BlocksSight>,
         WriteStorage<'a, Viewshed>,
     );
 
-    fn run(&mut self, (player, mut map, entities, coordinates, mut viewsheds): Self::SystemData) {
+    fn run(
+        &mut self,
+        (player, mut map, entities, coordinates, sight_blockers, mut viewsheds): Self::SystemData,
+    ) {
         use ComponentEvent::*;
 
-        let changed: BitSet = coordinates
+        let mut changed: BitSet = coordinates
             .channel()
             .read(&mut self.cursor)
             .filter_map(|&event| match event {
@@ -56,10 +76,23 @@ impl<'a> System<'a> for VisibilitySystem {
             })
             .collect();
 
+        // Anything could have come into view when a sight blocker goes away.
+        let sight_changed = sight_blockers
+            .channel()
+            .read(&mut self.sight_blocker_cursor)
+            .count()
+            > 0;
+
+        for (entity, vs) in (&entities, &mut viewsheds).join() {
+            if std::mem::take(&mut vs.dirty) || sight_changed {
+                changed.add(entity.id());
+            }
+        }
+
         for (entity, &coord, vs, _) in (&entities, &coordinates, &mut viewsheds, &changed).join() {
             log::trace!("Updating FOV for {entity:?}");
 
-            vs.visible_tiles = map.field_of_view(coord, vs.range);
+            vs.visible_tiles = map.field_of_view(coord, vs.range());
 
             if entity == *player {
                 for &coord in vs.visible_tiles.iter() {
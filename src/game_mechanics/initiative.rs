@@ -1,13 +1,29 @@
+use super::Stat;
 use crate::prelude::*;
 
 #[derive(Component, Default)]
 #[storage(NullStorage)]
 pub struct HasInitiative;
 
+/// Item use time starts at a full turn
+const BASE_ITEM_USE_TIME: i32 = 100;
+
 #[derive(Component)]
 pub struct Initiative {
     pub current: i32,
-    pub speed: i32,
+    pub speed: Stat,
+    /// Percent of a turn spent using an item
+    pub item_use_time: Stat,
+}
+
+impl Initiative {
+    pub fn new(current: i32, speed: i32) -> Self {
+        Self {
+            current,
+            speed: Stat::new(speed),
+            item_use_time: Stat::new(BASE_ITEM_USE_TIME),
+        }
+    }
 }
 
 #[derive(SystemData)]
@@ -25,7 +41,17 @@ impl<'a> InitiativeData<'a> {
     pub fn spend_turn(&mut self, entity: Entity) {
         if self.has_initiative.remove(entity).is_some() {
             if let Some(initiative) = self.initiatives.get_mut(entity) {
-                initiative.current = initiative.speed;
+                initiative.current = initiative.speed.value();
+            }
+        }
+    }
+
+    /// Like a normal turn, but shortened by anything that speeds up item use
+    pub fn spend_item_turn(&mut self, entity: Entity) {
+        if self.has_initiative.remove(entity).is_some() {
+            if let Some(initiative) = self.initiatives.get_mut(entity) {
+                let speed = initiative.speed.value() * initiative.item_use_time.value() / 100;
+                initiative.current = i32::max(1, speed);
             }
         }
     }
@@ -1,25 +1,279 @@
-use crate::prelude::*;
+use super::{Container, WantsToEquip};
+use crate::{
+    entity::{SpawnBuilder, StartingInventory},
+    prelude::*,
+};
+use specs::storage::MaskedStorage;
+use std::ops::Deref;
 
+/// One item for each inventory letter
+pub const INVENTORY_CAPACITY: usize = 26;
+
+/// Queued on the item, so that a whole pile can be picked up at once.
 #[derive(Component)]
 pub struct WantsToPickUp(pub(super) Entity);
 
+/// Queued on the item, which is dropped by whoever has it in their inventory.
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct WantsToDrop;
+
+/// How far from where its owner died an item can land
+const SCATTER_RADIUS: i32 = 2;
+
+/// Number keys the player can bind items to
+pub const QUICKBAR_SLOTS: usize = 10;
+
+/// The player's items by letter. Items keep their letter for as long as
+/// they're held, leaving gaps when they're used up or dropped.
+#[derive(Default)]
+pub struct Inventory(Vec<Option<Entity>>);
+
+impl Inventory {
+    pub fn label(index: usize) -> char {
+        (b'A' + index as u8) as char
+    }
+
+    pub fn get(&self, index: usize) -> Option<Entity> {
+        self.0.get(index).copied().flatten()
+    }
+
+    /// Every item along with its letter
+    pub fn labelled(&self) -> impl Iterator<Item = (char, Entity)> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| Some((Self::label(index), (*item)?)))
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.0.iter().flatten().copied()
+    }
+
+    pub fn count(&self) -> usize {
+        self.items().count()
+    }
+
+    pub fn contains(&self, item: Entity) -> bool {
+        self.items().any(|i| i == item)
+    }
+
+    pub(super) fn remove(&mut self, item: Entity) {
+        for slot in self.0.iter_mut().filter(|slot| **slot == Some(item)) {
+            *slot = None;
+        }
+    }
+
+    /// Frees the letters of items no longer held and gives new items the first
+    /// free letter.
+    fn update(&mut self, held: &[Entity]) {
+        for slot in &mut self.0 {
+            if slot.is_some_and(|item| !held.contains(&item)) {
+                *slot = None;
+            }
+        }
+
+        for &item in held {
+            if self.contains(item) {
+                continue;
+            }
+
+            match self.0.iter_mut().find(|slot| slot.is_none()) {
+                Some(slot) => *slot = Some(item),
+                None => self.0.push(Some(item)),
+            }
+        }
+
+        while self.0.last() == Some(&None) {
+            self.0.pop();
+        }
+    }
+}
+
+/// Items bound to the number keys for one-key use
 #[derive(Default)]
-pub struct Inventory(pub Vec<Entity>);
+pub struct Quickbar([Option<Entity>; QUICKBAR_SLOTS]);
+
+impl Quickbar {
+    /// 1 to 9, then 0
+    pub fn label(slot: usize) -> char {
+        char::from_digit((slot as u32 + 1) % 10, 10).unwrap()
+    }
+
+    pub fn slot_for_key(key: VirtualKeyCode) -> Option<usize> {
+        use VirtualKeyCode::*;
+
+        [Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9, Key0]
+            .into_iter()
+            .position(|k| k == key)
+    }
+
+    pub fn get(&self, slot: usize) -> Option<Entity> {
+        self.0.get(slot).copied().flatten()
+    }
+
+    pub fn slot_of(&self, item: Entity) -> Option<usize> {
+        self.0.iter().position(|&bound| bound == Some(item))
+    }
+
+    /// Binds the item to the slot, moving it if it was already bound elsewhere
+    pub fn bind(&mut self, slot: usize, item: Entity) {
+        if let Some(old_slot) = self.slot_of(item) {
+            self.0[old_slot] = None;
+        }
+        self.0[slot] = Some(item);
+    }
+}
 
 #[derive(Component)]
 pub struct InInventory(pub(super) Entity);
 
+impl InInventory {
+    pub fn owner(&self) -> Entity {
+        self.0
+    }
+}
+
+/// Identical consumables share a single inventory line. Single items split off
+/// the stack are spawned from the builder.
+#[derive(Component)]
+pub struct Stack {
+    /// Only stacks of the same kind merge
+    kind: &'static str,
+    count: i32,
+    pub(super) builder: SpawnBuilder,
+}
+
+impl Stack {
+    pub fn new(kind: &'static str, builder: SpawnBuilder) -> Self {
+        Self {
+            kind,
+            count: 1,
+            builder,
+        }
+    }
+
+    pub fn count(&self) -> i32 {
+        self.count
+    }
+
+    /// Takes one item off the stack. Returns false if it was the last one, in
+    /// which case the stack entity itself is what's being taken.
+    pub(super) fn split(&mut self) -> bool {
+        if self.count > 1 {
+            self.count -= 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Spawns an item straight into the owner's inventory.
+pub fn give_item(world: &mut World, owner: Entity, builder: SpawnBuilder) -> Entity {
+    builder(world.create_entity())
+        .with(InInventory(owner))
+        .build()
+}
+
+/// Rolls what everyone with a starting inventory carries, equipping the
+/// first item for each slot. Containers just hold on to their items.
+pub fn fill_starting_inventories(world: &mut World) {
+    let owners: Vec<_> = {
+        let entities = world.entities();
+        let mut starting_inventories = world.write_storage::<StartingInventory>();
+
+        (&entities, starting_inventories.drain())
+            .join()
+            .map(|(owner, StartingInventory(table))| (owner, table))
+            .collect()
+    };
+
+    for (owner, table) in owners {
+        let wears_gear = !world.read_storage::<Container>().contains(owner);
+        let mut filled_slots = Vec::new();
+
+        for &(chance, builder) in table {
+            if world
+                .write_resource::<RandomNumberGenerator>()
+                .range(0, 100)
+                >= chance
+            {
+                continue;
+            }
+
+            let item = give_item(world, owner, builder);
+            let slot = world
+                .read_storage::<Equippable>()
+                .get(item)
+                .map(|&Equippable(slot)| slot);
+
+            if let Some(slot) = slot.filter(|slot| wears_gear && !filled_slots.contains(slot)) {
+                filled_slots.push(slot);
+                world
+                    .write_storage::<WantsToEquip>()
+                    .insert(item, WantsToEquip(owner))
+                    .unwrap();
+            }
+        }
+    }
+}
+
+/// Hexes to drop things on around the origin, nearest first. Only hexes that
+/// are open and have no items on them already are used, and once those run
+/// out everything else piles up on the origin.
+pub(super) fn drop_spots(
+    map: &Map,
+    origin: Coordinate,
+    items: &ReadStorage<Item>,
+) -> impl Iterator<Item = Coordinate> {
+    let mut spots: Vec<_> = map
+        .area_of_effect(origin, SCATTER_RADIUS)
+        .into_iter()
+        .filter(|&pos| pos == origin || !map[pos].is_blocked())
+        .filter(|&pos| map[pos].entity(items).is_none())
+        .collect();
+    spots.sort_by_key(|&pos| origin.distance(pos));
+
+    spots.into_iter().chain(std::iter::repeat(origin))
+}
+
+/// The stack already carried by `owner` that `item` would join if picked up
+pub fn carried_stack<I, S>(
+    item: Entity,
+    owner: Entity,
+    entities: &Entities,
+    in_inventories: &Storage<InInventory, I>,
+    stacks: &Storage<Stack, S>,
+) -> Option<Entity>
+where
+    I: Deref<Target = MaskedStorage<InInventory>>,
+    S: Deref<Target = MaskedStorage<Stack>>,
+{
+    let picked_up = stacks.get(item)?;
+
+    (entities, in_inventories, stacks)
+        .join()
+        .find(|&(other, &InInventory(carrier), other_stack)| {
+            other != item && carrier == owner && other_stack.kind == picked_up.kind
+        })
+        .map(|(stack, ..)| stack)
+}
+
 pub struct ItemPickupSystem;
 
 impl<'a> System<'a> for ItemPickupSystem {
     type SystemData = (
         ReadExpect<'a, Entity>,
-        WriteExpect<'a, GameLog>,
+        Write<'a, GameLog>,
         Entities<'a>,
         WriteStorage<'a, WantsToPickUp>,
         WriteStorage<'a, Coordinate>,
         ReadStorage<'a, Appearance>,
         WriteStorage<'a, InInventory>,
+        ReadStorage<'a, NaniteCache>,
+        WriteStorage<'a, Nanites>,
+        WriteStorage<'a, Stack>,
     );
 
     fn run(
@@ -32,20 +286,139 @@ impl<'a> System<'a> for ItemPickupSystem {
             mut positions,
             appearances,
             mut inventories,
+            nanite_caches,
+            mut nanites,
+            mut stacks,
         ): Self::SystemData,
     ) {
-        for (recipient, &WantsToPickUp(item)) in (&entities, &pickup_intents).join() {
+        for (item, &WantsToPickUp(recipient)) in (&entities, &pickup_intents).join() {
+            // Nanite caches are only any use to those who can absorb them
+            if let Some(&NaniteCache(amount)) = nanite_caches.get(item) {
+                if let Some(nanites) = nanites.get_mut(recipient) {
+                    positions.remove(item);
+                    nanites.gain(amount);
+                    entities.delete(item).unwrap();
+
+                    if recipient == *player {
+                        game_log.nanites_gained(amount, nanites);
+                    }
+                }
+
+                continue;
+            }
+
+            // Identical items join the stack already being carried
+            let existing_stack = carried_stack(item, recipient, &entities, &inventories, &stacks);
+
+            if let Some(stack) = existing_stack {
+                let count = stacks.get(item).unwrap().count;
+                stacks.get_mut(stack).unwrap().count += count;
+                entities.delete(item).unwrap();
+                positions.remove(item);
+                let giver = inventories.remove(item).map(|InInventory(giver)| giver);
+
+                log_pickup(&mut game_log, *player, giver, recipient, item, &appearances);
+
+                continue;
+            }
+
+            let carried = inventories
+                .join()
+                .filter(|&&InInventory(owner)| owner == recipient)
+                .count();
+
+            if carried >= INVENTORY_CAPACITY {
+                if recipient == *player {
+                    if let Some(item_appearance) = appearances.get(item) {
+                        game_log.inventory_full(item_appearance);
+                    }
+                }
+
+                continue;
+            }
+
             positions.remove(item);
-            inventories.insert(item, InInventory(recipient)).unwrap();
+            let giver = inventories
+                .insert(item, InInventory(recipient))
+                .unwrap()
+                .map(|InInventory(giver)| giver);
+
+            log_pickup(&mut game_log, *player, giver, recipient, item, &appearances);
+        }
+
+        pickup_intents.clear();
+    }
+}
+
+/// Items can come off the floor or out of someone else's inventory, e.g. when
+/// the player puts them in a container.
+fn log_pickup(
+    game_log: &mut GameLog,
+    player: Entity,
+    giver: Option<Entity>,
+    recipient: Entity,
+    item: Entity,
+    appearances: &ReadStorage<Appearance>,
+) {
+    let Some(item_appearance) = appearances.get(item) else {
+        return;
+    };
+
+    let Some(recipient_appearance) = appearances.get(recipient) else {
+        return;
+    };
 
-            if recipient == *player {
+    if recipient == player {
+        game_log.player_pickup(item_appearance);
+    } else if giver == Some(player) {
+        game_log.stored(item_appearance, recipient_appearance);
+    } else {
+        game_log.pickup(recipient_appearance, item_appearance);
+    }
+}
+
+pub struct ItemDropSystem;
+
+impl<'a> System<'a> for ItemDropSystem {
+    type SystemData = (
+        ReadExpect<'a, Entity>,
+        Write<'a, GameLog>,
+        Entities<'a>,
+        WriteStorage<'a, WantsToDrop>,
+        WriteStorage<'a, Coordinate>,
+        ReadStorage<'a, Appearance>,
+        WriteStorage<'a, InInventory>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            player,
+            mut game_log,
+            entities,
+            mut drop_intents,
+            mut positions,
+            appearances,
+            mut inventories,
+        ): Self::SystemData,
+    ) {
+        for (item, _) in (&entities, &drop_intents).join() {
+            let Some(InInventory(owner)) = inventories.remove(item) else {
+                continue;
+            };
+
+            if let Some(&pos) = positions.get(owner) {
+                positions.insert(item, pos).unwrap();
+            }
+
+            if owner == *player {
                 if let Some(item_appearance) = appearances.get(item) {
-                    game_log.player_pickup(item_appearance);
+                    game_log.player_drop(item_appearance);
                 }
             }
         }
 
-        pickup_intents.clear();
+        drop_intents.clear();
     }
 }
 
@@ -54,25 +427,97 @@ pub struct PlayerInventorySystem;
 impl<'a> System<'a> for PlayerInventorySystem {
     type SystemData = (
         Write<'a, Inventory>,
+        Write<'a, Quickbar>,
         ReadExpect<'a, Entity>,
         Entities<'a>,
         WriteStorage<'a, InInventory>,
     );
 
-    fn run(&mut self, (mut inventory, player, entities, in_inventories): Self::SystemData) {
+    fn run(
+        &mut self,
+        (mut inventory, mut quickbar, player, entities, in_inventories): Self::SystemData,
+    ) {
         let player_inventory: Vec<_> = (&entities, &in_inventories)
             .join()
-            .filter_map(|(item, &InInventory(owner))| (owner == *player).then(|| item))
+            .filter(|&(_, &InInventory(owner))| owner == *player)
+            .map(|(item, _)| item)
             .collect();
 
-        inventory
-            .0
-            .retain(|&item| entities.is_alive(item) && player_inventory.contains(&item));
+        inventory.update(&player_inventory);
 
-        for item in player_inventory {
-            if !inventory.0.contains(&item) {
-                inventory.0.push(item);
+        for slot in &mut quickbar.0 {
+            if slot.is_some_and(|item| !inventory.contains(item)) {
+                *slot = None;
             }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn items_keep_their_letters() {
+        let mut world = World::new();
+        let [a, b, c, d] = [(); 4].map(|_| world.create_entity().build());
+        let mut inventory = Inventory::default();
+
+        inventory.update(&[a, b, c]);
+        inventory.update(&[a, c]);
+        assert_eq!(
+            inventory.labelled().collect::<Vec<_>>(),
+            [('A', a), ('C', c)]
+        );
+
+        inventory.update(&[a, c, d]);
+        assert_eq!(
+            inventory.labelled().collect::<Vec<_>>(),
+            [('A', a), ('B', d), ('C', c)]
+        );
+    }
+
+    #[test]
+    fn only_the_same_kind_stacks() {
+        fn item(entity: EntityBuilder) -> EntityBuilder {
+            entity
+        }
+
+        let mut world = World::new();
+        world.register::<InInventory>();
+        world.register::<Stack>();
+        let owner = world.create_entity().build();
+        let carried = world
+            .create_entity()
+            .with(Stack::new("battery", item))
+            .with(InInventory(owner))
+            .build();
+        let [battery, grenade] = ["battery", "grenade"]
+            .map(|kind| world.create_entity().with(Stack::new(kind, item)).build());
+
+        let stack_for = |item| {
+            carried_stack(
+                item,
+                owner,
+                &world.entities(),
+                &world.read_storage(),
+                &world.read_storage(),
+            )
+        };
+
+        assert_eq!(stack_for(battery), Some(carried));
+        assert_eq!(stack_for(grenade), None);
+    }
+
+    #[test]
+    fn rebinding_moves_an_item() {
+        let item = World::new().create_entity().build();
+        let mut quickbar = Quickbar::default();
+
+        quickbar.bind(0, item);
+        quickbar.bind(9, item);
+
+        assert_eq!(quickbar.get(0), None);
+        assert_eq!(quickbar.slot_of(item).map(Quickbar::label), Some('0'));
+    }
+}
@@ -0,0 +1,108 @@
+use super::is_legal_move;
+use crate::prelude::*;
+use std::collections::HashMap;
+
+/// Extra damage dealt for each hex of push that was cut short by a collision
+const COLLISION_DAMAGE: i32 = 3;
+
+/// Effects with knockback push their targets away from the point of impact.
+#[derive(Component)]
+pub struct Knockback(pub i32);
+
+#[derive(Component)]
+pub struct Pushed {
+    pub(super) origin: Coordinate,
+    pub(super) distance: i32,
+}
+
+pub struct KnockbackSystem;
+
+impl<'a> System<'a> for KnockbackSystem {
+    type SystemData = (
+        ReadExpect<'a, Map>,
+        Entities<'a>,
+        WriteStorage<'a, Pushed>,
+        WriteStorage<'a, Coordinate>,
+        WriteStorage<'a, Durability>,
+        ReadStorage<'a, BlocksTile>,
+        ReadStorage<'a, Appearance>,
+        Write<'a, GameLog>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            map,
+            entities,
+            mut pushes,
+            mut positions,
+            mut durabilities,
+            blockers,
+            appearances,
+            mut game_log,
+        ): Self::SystemData,
+    ) {
+        // The map index is only updated next tick, so hexes taken by earlier
+        // pushes this tick are tracked here.
+        let mut claimed: HashMap<Coordinate, Entity> = HashMap::new();
+
+        for (entity, &Pushed { origin, distance }) in (&entities, &pushes).join() {
+            let pos = match positions.get(entity) {
+                Some(&pos) => pos,
+                None => continue,
+            };
+
+            // Entities standing right at the point of impact have nowhere to go.
+            let direction = match origin.direction_to(pos) {
+                Some(direction) => direction,
+                None => continue,
+            };
+
+            let mut dest = pos;
+            let mut collision: Option<(Coordinate, i32)> = None;
+
+            for (step, coord) in pos
+                .line_to(pos.step(direction, distance))
+                .enumerate()
+                .skip(1)
+            {
+                if !is_legal_move(&map, coord) || claimed.contains_key(&coord) {
+                    collision = Some((coord, distance - step as i32 + 1));
+                    break;
+                }
+
+                dest = coord;
+            }
+
+            if dest != pos {
+                log::debug!("{entity:?} was pushed to {dest:?}");
+                *positions.get_mut(entity).unwrap() = dest;
+                claimed.insert(dest, entity);
+            }
+
+            if let Some((coord, unspent_distance)) = collision {
+                let damage = COLLISION_DAMAGE * unspent_distance;
+                let obstacle = claimed
+                    .get(&coord)
+                    .copied()
+                    .or_else(|| {
+                        map.in_bounds(coord)
+                            .then(|| map[coord].entity(&blockers))
+                            .flatten()
+                    })
+                    .filter(|&obstacle| obstacle != entity);
+
+                for victim in [Some(entity), obstacle].into_iter().flatten() {
+                    if let Some(durability) = durabilities.get_mut(victim) {
+                        let damage_taken = durability.take_damage(damage);
+                        if let Some(victim_name) = appearances.get(victim) {
+                            game_log.collision(victim_name, damage_taken);
+                        }
+                    }
+                }
+            }
+        }
+
+        pushes.clear();
+    }
+}
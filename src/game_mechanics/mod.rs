@@ -1,40 +1,75 @@
+mod container;
 mod durability;
 mod effect;
+mod equipment;
 mod field_of_view;
 mod initiative;
 mod inventory;
+mod knockback;
 mod movement;
+mod nanites;
+mod station;
+mod stats;
+mod suit_upgrades;
+mod throwing;
+mod tile_effect;
+mod weapon_upgrades;
 
+pub use container::*;
 pub use durability::*;
 pub use effect::*;
+pub use equipment::*;
 pub use field_of_view::*;
 pub use initiative::*;
 pub use inventory::*;
+pub use knockback::*;
 pub use movement::*;
+pub use nanites::*;
+pub use station::*;
+pub use stats::*;
+pub use suit_upgrades::*;
+pub use throwing::*;
+pub use tile_effect::*;
+pub use weapon_upgrades::*;
 
 use crate::{ai::MonsterAISystem, map::IndexMapSystem, prelude::*, targeting::ClearTargetSystem};
 
 pub fn dispatcher<'a, 'b>(world: &mut World) -> Dispatcher<'a, 'b> {
     DispatcherBuilder::new()
         .with(InitiativeSystem, "initiative", &[])
-        .with(MonsterAISystem, "monster_ai", &["initiative"])
+        .with(ChillSystem, "chill", &["initiative"])
+        .with(MonsterAISystem, "monster_ai", &["initiative", "chill"])
         .with(MovementSystem, "movement", &["monster_ai"])
         .with(ItemPickupSystem, "item_pickup", &[])
-        .with(EffectUseSystem, "effect_use", &[])
-        .with(VisibilitySystem::new(world), "visibility", &["movement"])
-        .with(ShieldRegenSystem, "shield_regen", &["effect_use"])
-        .with(DeathSystem, "death", &["effect_use"])
+        .with(OpenContainerSystem, "open_container", &[])
+        .with(EquipSystem, "equip", &["item_pickup"])
+        .with(ItemDropSystem, "item_drop", &["equip"])
+        .with(ThrowSystem, "throw", &["equip"])
+        .with(TileEffectSystem, "tile_effect", &["initiative"])
         .with(
-            PlayerInventorySystem,
-            "player_inventory",
-            &["item_pickup", "effect_use"],
+            EffectUseSystem,
+            "effect_use",
+            &["equip", "throw", "tile_effect"],
         )
-        .with(ClearTargetSystem, "clear_target", &["visibility", "death"])
+        .with(KnockbackSystem, "knockback", &["movement", "effect_use"])
+        .with(DeathSystem, "death", &["knockback"])
         .with(
             IndexMapSystem,
             "index_map",
-            &["movement", "visibility", "death"],
+            &["movement", "knockback", "death"],
+        )
+        .with(
+            VisibilitySystem::new(world),
+            "visibility",
+            &["movement", "knockback", "index_map"],
         )
+        .with(RegenSystem, "regen", &["knockback"])
+        .with(
+            PlayerInventorySystem,
+            "player_inventory",
+            &["item_pickup", "item_drop", "effect_use", "open_container"],
+        )
+        .with(ClearTargetSystem, "clear_target", &["visibility", "death"])
         .build()
 }
 
@@ -42,6 +77,11 @@ pub fn dispatcher<'a, 'b>(world: &mut World) -> Dispatcher<'a, 'b> {
 pub struct Intents<'a> {
     wants_to_move: WriteStorage<'a, WantsToMove>,
     wants_to_pick_up: WriteStorage<'a, WantsToPickUp>,
+    wants_to_equip: WriteStorage<'a, WantsToEquip>,
+    wants_to_unequip: WriteStorage<'a, WantsToUnequip>,
+    wants_to_throw: WriteStorage<'a, WantsToThrow>,
+    wants_to_drop: WriteStorage<'a, WantsToDrop>,
+    wants_to_open: WriteStorage<'a, WantsToOpen>,
 }
 
 impl<'a> Intents<'a> {
@@ -53,7 +93,37 @@ impl<'a> Intents<'a> {
 
     pub fn wants_to_pick_up(&mut self, recipient: Entity, item: Entity) {
         self.wants_to_pick_up
-            .insert(recipient, WantsToPickUp(item))
+            .insert(item, WantsToPickUp(recipient))
             .expect("could not queue item pickup intent");
     }
+
+    pub fn wants_to_equip(&mut self, wearer: Entity, item: Entity) {
+        self.wants_to_equip
+            .insert(item, WantsToEquip(wearer))
+            .expect("could not queue equip intent");
+    }
+
+    pub fn wants_to_unequip(&mut self, item: Entity) {
+        self.wants_to_unequip
+            .insert(item, WantsToUnequip)
+            .expect("could not queue unequip intent");
+    }
+
+    pub fn wants_to_drop(&mut self, item: Entity) {
+        self.wants_to_drop
+            .insert(item, WantsToDrop)
+            .expect("could not queue drop intent");
+    }
+
+    pub fn wants_to_throw(&mut self, item: Entity, target: Coordinate) {
+        self.wants_to_throw
+            .insert(item, WantsToThrow(target))
+            .expect("could not queue throw intent");
+    }
+
+    pub fn wants_to_open(&mut self, opener: Entity, container: Entity) {
+        self.wants_to_open
+            .insert(container, WantsToOpen(opener))
+            .expect("could not queue open intent");
+    }
 }
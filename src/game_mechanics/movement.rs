@@ -12,14 +12,47 @@ pub struct MovementSystem;
 impl<'a> System<'a> for MovementSystem {
     type SystemData = (
         ReadExpect<'a, Map>,
+        ReadExpect<'a, Entity>,
+        Write<'a, GameLog>,
+        Entities<'a>,
         WriteStorage<'a, WantsToMove>,
         WriteStorage<'a, Coordinate>,
+        ReadStorage<'a, Item>,
+        ReadStorage<'a, Appearance>,
     );
 
-    fn run(&mut self, (map, mut move_intents, mut coordinates): Self::SystemData) {
-        for (&WantsToMove(dest), coord) in (&move_intents, &mut coordinates).join() {
-            if is_legal_move(&map, dest) {
-                *coord = dest;
+    fn run(
+        &mut self,
+        (
+            map,
+            player,
+            mut game_log,
+            entities,
+            mut move_intents,
+            mut coordinates,
+            items,
+            appearances,
+        ): Self::SystemData,
+    ) {
+        for (entity, &WantsToMove(dest), coord) in
+            (&entities, &move_intents, &mut coordinates).join()
+        {
+            if !is_legal_move(&map, dest) {
+                continue;
+            }
+
+            *coord = dest;
+
+            // Only the top item of a pile is drawn, so list the rest
+            if entity == *player {
+                let pile: Vec<_> = map[dest]
+                    .entities(&items)
+                    .filter_map(|item| appearances.get(item))
+                    .collect();
+
+                if pile.len() > 1 {
+                    game_log.items_here(&pile);
+                }
             }
         }
 
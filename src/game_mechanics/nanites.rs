@@ -0,0 +1,58 @@
+use crate::prelude::*;
+use anyhow::{ensure, Result};
+
+/// A pile of nanites waiting to be collected.
+#[derive(Component)]
+pub struct NaniteCache(pub i32);
+
+/// Nanites serve as both currency and experience.
+#[derive(Component, Default)]
+pub struct Nanites {
+    balance: i32,
+    /// Lifetime total, never reduced by spending
+    collected: i32,
+}
+
+impl Nanites {
+    pub fn balance(&self) -> i32 {
+        self.balance
+    }
+
+    pub fn collected(&self) -> i32 {
+        self.collected
+    }
+
+    pub fn gain(&mut self, amount: i32) {
+        self.balance += amount;
+        self.collected += amount;
+    }
+
+    pub fn spend(&mut self, amount: i32) -> Result<()> {
+        ensure!(
+            self.balance >= amount,
+            "not enough nanites ({amount} needed)"
+        );
+
+        self.balance -= amount;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, test_case::test_case};
+
+    #[test_case(5 => (true, 5); "affordable")]
+    #[test_case(10 => (true, 0); "exact balance")]
+    #[test_case(11 => (false, 10); "too expensive")]
+    fn spending_requires_enough_nanites(cost: i32) -> (bool, i32) {
+        let mut nanites = Nanites::default();
+        nanites.gain(10);
+
+        let spent = nanites.spend(cost).is_ok();
+
+        assert_eq!(nanites.collected(), 10);
+        (spent, nanites.balance())
+    }
+}
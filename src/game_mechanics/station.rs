@@ -0,0 +1,267 @@
+use super::{
+    level_up_weapon, Charges, DamageBonus, InInventory, Loadout, Stack, StatKind, SuitUpgrade,
+    SuitUpgrades, UpgradeLevel, WEAPON_UPGRADE_COST,
+};
+use crate::prelude::*;
+use anyhow::{ensure, Context, Result};
+
+/// Nanites per point of durability repaired
+const REPAIR_COST: i32 = 1;
+/// Nanites per point of shield recharged
+const RECHARGE_COST: i32 = 1;
+/// Nanites per item charge restored
+const CHARGE_COST: i32 = 2;
+const SUIT_UPGRADE_DURABILITY: i32 = 5;
+
+/// Stations trade the player's nanites for services.
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum Station {
+    Repair,
+    Recharge,
+    Recycle,
+    Upgrade,
+}
+
+/// How many nanites an item is worth at a recycle station
+#[derive(Component)]
+pub struct RecycleValue(pub i32);
+
+#[derive(SystemData)]
+pub struct StationUsage<'a> {
+    player: ReadExpect<'a, Entity>,
+    entities: Entities<'a>,
+    game_log: Write<'a, GameLog>,
+    nanites: WriteStorage<'a, Nanites>,
+    durabilities: WriteStorage<'a, Durability>,
+    deals_damage: WriteStorage<'a, DealsDamage>,
+    damage_bonuses: WriteStorage<'a, DamageBonus>,
+    suit_upgrades: WriteStorage<'a, SuitUpgrades>,
+    mod_slots: WriteStorage<'a, ModSlots>,
+    recycle_values: WriteStorage<'a, RecycleValue>,
+    upgrade_levels: WriteStorage<'a, UpgradeLevel>,
+    charges: WriteStorage<'a, Charges>,
+    in_inventories: WriteStorage<'a, InInventory>,
+    stacks: WriteStorage<'a, Stack>,
+    inventory: Write<'a, Inventory>,
+    appearances: WriteStorage<'a, Appearance>,
+    loadout: Loadout<'a>,
+}
+
+impl<'a> StationUsage<'a> {
+    pub fn balance(&self) -> i32 {
+        self.nanites.get(*self.player).map_or(0, Nanites::balance)
+    }
+
+    /// The cost to fully repair the player's suit
+    pub fn repair_cost(&self) -> i32 {
+        let (health, max_health) = self.player_durability().health();
+
+        (max_health - health) * REPAIR_COST
+    }
+
+    /// Repairs as much of the player's suit as they can afford
+    pub fn repair(&mut self) -> Result<()> {
+        let (health, max_health) = self.player_durability().health();
+        ensure!(health < max_health, "suit is already fully repaired");
+
+        let amount = i32::min(max_health - health, self.balance() / REPAIR_COST);
+        ensure!(amount > 0, "not enough nanites");
+        self.spend(amount * REPAIR_COST, "repairs")?;
+
+        let amount = self.player_durability_mut().heal(amount);
+        self.game_log.repaired(amount);
+
+        Ok(())
+    }
+
+    /// The cost to fully recharge the player's shield
+    pub fn recharge_cost(&self) -> i32 {
+        self.player_durability()
+            .shield()
+            .map_or(0, |(shield, max_shield)| {
+                (max_shield - shield) * RECHARGE_COST
+            })
+    }
+
+    pub fn recharge_shield(&mut self) -> Result<()> {
+        let (shield, max_shield) = self.player_durability().shield().context("no shield")?;
+        ensure!(shield < max_shield, "shield is already fully charged");
+
+        let amount = i32::min(max_shield - shield, self.balance() / RECHARGE_COST);
+        ensure!(amount > 0, "not enough nanites");
+        self.spend(amount * RECHARGE_COST, "recharging")?;
+
+        let amount = self.player_durability_mut().recharge_shield(amount);
+        self.game_log.recharged(amount);
+
+        Ok(())
+    }
+
+    /// The player's items with charges, along with the cost to refill them
+    pub fn rechargeables(&self) -> impl Iterator<Item = (char, &Appearance, Option<i32>)> + '_ {
+        self.inventory.labelled().filter_map(|(label, item)| {
+            let appearance = self.appearances.get(item)?;
+            let cost = self
+                .charges
+                .get(item)
+                .map(|charges| (charges.max() - charges.current()) * CHARGE_COST);
+
+            Some((label, appearance, cost))
+        })
+    }
+
+    /// Restores as many of the item's charges as the player can afford
+    pub fn recharge_item(&mut self, index: usize) -> Result<()> {
+        let item = self.inventory.get(index).context("no such item")?;
+        let charges = self.charges.get(item).context("doesn't use charges")?;
+        ensure!(!charges.is_full(), "already fully charged");
+
+        let amount = i32::min(
+            charges.max() - charges.current(),
+            self.balance() / CHARGE_COST,
+        );
+        ensure!(amount > 0, "not enough nanites");
+        self.spend(amount * CHARGE_COST, "recharging")?;
+
+        let amount = self.charges.get_mut(item).unwrap().recharge(amount);
+        if let Some(appearance) = self.appearances.get(item) {
+            self.game_log.item_recharged(appearance, amount);
+        }
+
+        Ok(())
+    }
+
+    /// The player's items along with their recycle value, if any
+    pub fn recyclables(&self) -> impl Iterator<Item = (char, &Appearance, Option<i32>)> + '_ {
+        self.inventory.labelled().filter_map(|(label, item)| {
+            let appearance = self.appearances.get(item)?;
+            let value = self.recycle_values.get(item).map(|&RecycleValue(v)| v);
+
+            Some((label, appearance, value))
+        })
+    }
+
+    pub fn recycle(&mut self, index: usize) -> Result<()> {
+        let item = self.inventory.get(index).context("no such item")?;
+        let &RecycleValue(value) = self.recycle_values.get(item).context("can't be recycled")?;
+        ensure!(!self.loadout.is_equipped(item), "unequip it first");
+
+        let nanites = self.nanites.get_mut(*self.player).context("no nanites")?;
+        nanites.gain(value);
+
+        if let Some(appearance) = self.appearances.get(item) {
+            self.game_log.recycled(appearance, value, nanites);
+        }
+
+        // Stacks are recycled one item at a time
+        if !self.stacks.get_mut(item).is_some_and(Stack::split) {
+            self.in_inventories.remove(item);
+            self.inventory.remove(item);
+            self.entities.delete(item)?;
+        }
+
+        Ok(())
+    }
+
+    /// Each suit upgrade with its current level and the cost of the next one
+    pub fn suit_upgrades(&self) -> impl Iterator<Item = (SuitUpgrade, i32, Option<i32>)> + '_ {
+        SuitUpgrade::ALL.into_iter().map(|upgrade| {
+            let level = self
+                .suit_upgrades
+                .get(*self.player)
+                .map_or(0, |upgrades| upgrades.level(upgrade));
+
+            (upgrade, level, upgrade.cost(level))
+        })
+    }
+
+    pub fn upgrade_suit(&mut self, index: usize) -> Result<()> {
+        let (upgrade, level, cost) = self.suit_upgrades().nth(index).context("no such upgrade")?;
+        let cost = cost.context("already fully upgraded")?;
+        ensure!(
+            self.suit_upgrades.contains(*self.player),
+            "can't be upgraded"
+        );
+
+        self.spend(cost, "a suit upgrade")?;
+
+        let player = *self.player;
+        match upgrade {
+            SuitUpgrade::Durability => self
+                .player_durability_mut()
+                .reinforce(SUIT_UPGRADE_DURABILITY),
+            SuitUpgrade::Armor => self
+                .player_durability_mut()
+                .modify(StatKind::Defense, |defense| defense.increase_base(1)),
+            SuitUpgrade::ServoMotors | SuitUpgrade::TargetingAi => {
+                let bonus = self
+                    .damage_bonuses
+                    .get_mut(player)
+                    .context("no damage bonus")?;
+                match upgrade {
+                    SuitUpgrade::ServoMotors => bonus.melee.increase_base(1),
+                    _ => bonus.ranged.increase_base(1),
+                }
+            }
+            SuitUpgrade::ModSlots => {
+                self.mod_slots.get_mut(player).context("no mod slots")?.0 += 1;
+            }
+        }
+
+        self.suit_upgrades
+            .get_mut(player)
+            .unwrap()
+            .level_up(upgrade);
+        self.game_log.suit_upgraded(upgrade, level + 1);
+
+        Ok(())
+    }
+
+    pub fn weapon_upgrade_cost(&self) -> i32 {
+        WEAPON_UPGRADE_COST
+    }
+
+    /// Upgrades the weapon the player has equipped in the given slot
+    pub fn upgrade_weapon(&mut self, slot: EquipmentSlot) -> Result<()> {
+        let weapon = self
+            .loadout
+            .in_slot(*self.player, slot)
+            .next()
+            .context("no weapon equipped")?;
+        ensure!(
+            self.upgrade_levels.contains(weapon) && self.deals_damage.contains(weapon),
+            "can't be upgraded"
+        );
+
+        self.spend(WEAPON_UPGRADE_COST, "a weapon upgrade")?;
+        level_up_weapon(
+            self.upgrade_levels.get_mut(weapon).unwrap(),
+            self.deals_damage.get_mut(weapon).unwrap(),
+            self.charges.get_mut(weapon),
+            self.recycle_values.get_mut(weapon),
+            self.appearances.get_mut(weapon),
+        );
+
+        if let Some(appearance) = self.appearances.get(weapon) {
+            self.game_log.weapon_upgraded(appearance);
+        }
+
+        Ok(())
+    }
+
+    fn spend(&mut self, amount: i32, purpose: &str) -> Result<()> {
+        let nanites = self.nanites.get_mut(*self.player).context("no nanites")?;
+        nanites.spend(amount)?;
+        self.game_log.nanites_spent(amount, purpose, nanites);
+
+        Ok(())
+    }
+
+    fn player_durability(&self) -> &Durability {
+        self.durabilities.get(*self.player).unwrap()
+    }
+
+    fn player_durability_mut(&mut self) -> &mut Durability {
+        self.durabilities.get_mut(*self.player).unwrap()
+    }
+}
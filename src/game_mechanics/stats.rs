@@ -0,0 +1,236 @@
+use crate::prelude::*;
+use std::fmt;
+
+/// A change to a stat from some source, e.g. a piece of gear or a status effect
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Modifier {
+    Flat(i32),
+    Percent(i32),
+}
+
+impl fmt::Display for Modifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flat(amount) => write!(f, "{amount:+}"),
+            Self::Percent(amount) => write!(f, "{amount:+}%"),
+        }
+    }
+}
+
+/// A number with a base value plus modifiers which can be removed again.
+///
+/// Flat modifiers are added to the base before percentage modifiers apply.
+#[derive(Clone, Debug, Default)]
+pub struct Stat {
+    base: i32,
+    modifiers: Vec<(Entity, Modifier)>,
+}
+
+impl Stat {
+    pub const fn new(base: i32) -> Self {
+        Self {
+            base,
+            modifiers: Vec::new(),
+        }
+    }
+
+    /// The effective value after all modifiers
+    pub fn value(&self) -> i32 {
+        let (flat, percent) =
+            self.modifiers
+                .iter()
+                .fold((0, 0), |(flat, percent), (_, modifier)| match modifier {
+                    Modifier::Flat(amount) => (flat + amount, percent),
+                    Modifier::Percent(amount) => (flat, percent + amount),
+                });
+
+        (self.base + flat) * (100 + percent) / 100
+    }
+
+    /// Permanently changes the base value
+    pub fn increase_base(&mut self, amount: i32) {
+        self.base += amount;
+    }
+
+    pub fn add_modifier(&mut self, source: Entity, modifier: Modifier) {
+        self.modifiers.push((source, modifier));
+    }
+
+    pub fn remove_modifiers(&mut self, source: Entity) {
+        self.modifiers.retain(|&(s, _)| s != source);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatKind {
+    MaxHealth,
+    Defense,
+    MaxShield,
+    ShieldDefense,
+    ShieldRegen,
+    Speed,
+    MeleeDamage,
+    RangedDamage,
+    HealthRegen,
+    ViewRange,
+    ItemUseTime,
+}
+
+impl StatKind {
+    pub const ALL: [StatKind; 11] = [
+        Self::MaxHealth,
+        Self::Defense,
+        Self::MaxShield,
+        Self::ShieldDefense,
+        Self::ShieldRegen,
+        Self::Speed,
+        Self::MeleeDamage,
+        Self::RangedDamage,
+        Self::HealthRegen,
+        Self::ViewRange,
+        Self::ItemUseTime,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MaxHealth => "max durability",
+            Self::Defense => "defense",
+            Self::MaxShield => "max shield",
+            Self::ShieldDefense => "shield defense",
+            Self::ShieldRegen => "shield regen",
+            Self::Speed => "turn delay",
+            Self::MeleeDamage => "melee damage",
+            Self::RangedDamage => "ranged damage",
+            Self::HealthRegen => "durability regen",
+            Self::ViewRange => "view range",
+            Self::ItemUseTime => "item use time",
+        }
+    }
+}
+
+/// Extra damage dealt by whoever wields a weapon, on top of the weapon's own
+#[derive(Component, Default)]
+pub struct DamageBonus {
+    pub melee: Stat,
+    pub ranged: Stat,
+}
+
+/// Modifiers applied to whoever equips or is affected by this entity
+#[derive(Component)]
+pub struct StatModifiers(pub Vec<(StatKind, Modifier)>);
+
+impl fmt::Display for StatModifiers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (kind, modifier)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{modifier} {}", kind.label())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Routes stat changes to whichever component holds the stat.
+#[derive(SystemData)]
+pub struct StatData<'a> {
+    durabilities: WriteStorage<'a, Durability>,
+    initiatives: WriteStorage<'a, Initiative>,
+    damage_bonuses: WriteStorage<'a, DamageBonus>,
+    viewsheds: WriteStorage<'a, Viewshed>,
+}
+
+impl<'a> StatData<'a> {
+    /// Applies all of the source's modifiers to the entity
+    pub fn apply(&mut self, entity: Entity, source: Entity, modifiers: &StatModifiers) {
+        for &(kind, modifier) in modifiers.0.iter() {
+            self.modify(entity, kind, |stat| stat.add_modifier(source, modifier));
+        }
+    }
+
+    /// Removes all modifiers which came from the source
+    pub fn remove(&mut self, entity: Entity, source: Entity) {
+        for kind in StatKind::ALL {
+            self.modify(entity, kind, |stat| stat.remove_modifiers(source));
+        }
+    }
+
+    fn modify(&mut self, entity: Entity, kind: StatKind, f: impl FnOnce(&mut Stat)) {
+        use StatKind::*;
+
+        match kind {
+            MaxHealth | Defense | MaxShield | ShieldDefense | ShieldRegen | HealthRegen => {
+                if let Some(durability) = self.durabilities.get_mut(entity) {
+                    durability.modify(kind, f);
+                }
+            }
+            Speed | ItemUseTime => {
+                if let Some(initiative) = self.initiatives.get_mut(entity) {
+                    f(match kind {
+                        Speed => &mut initiative.speed,
+                        _ => &mut initiative.item_use_time,
+                    });
+                }
+            }
+            ViewRange => {
+                if let Some(viewshed) = self.viewsheds.get_mut(entity) {
+                    viewshed.modify_range(f);
+                }
+            }
+            MeleeDamage | RangedDamage => {
+                if let Some(bonus) = self.damage_bonuses.get_mut(entity) {
+                    f(match kind {
+                        MeleeDamage => &mut bonus.melee,
+                        _ => &mut bonus.ranged,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, test_case::test_case};
+
+    #[test_case(&[] => 10; "no modifiers")]
+    #[test_case(&[Modifier::Flat(5)] => 15; "flat bonus")]
+    #[test_case(&[Modifier::Flat(-3), Modifier::Flat(1)] => 8; "flat penalties and bonuses")]
+    #[test_case(&[Modifier::Percent(50)] => 15; "percent bonus")]
+    #[test_case(&[Modifier::Percent(50), Modifier::Flat(2)] => 18; "flat applies before percent")]
+    fn stacks_modifiers(modifiers: &[Modifier]) -> i32 {
+        let source = World::new().create_entity().build();
+        let mut stat = Stat::new(10);
+
+        for &modifier in modifiers {
+            stat.add_modifier(source, modifier);
+        }
+
+        stat.value()
+    }
+
+    #[test]
+    fn removes_modifiers_by_source() {
+        let mut world = World::new();
+        let (gear, status_effect) = (world.create_entity().build(), world.create_entity().build());
+        let mut stat = Stat::new(10);
+
+        stat.add_modifier(gear, Modifier::Flat(5));
+        stat.add_modifier(status_effect, Modifier::Percent(-50));
+        stat.add_modifier(gear, Modifier::Flat(1));
+        stat.remove_modifiers(gear);
+
+        assert_eq!(stat.value(), 5);
+    }
+
+    #[test]
+    fn describes_modifiers() {
+        let modifiers = StatModifiers(vec![
+            (StatKind::ShieldRegen, Modifier::Flat(3)),
+            (StatKind::ItemUseTime, Modifier::Percent(-40)),
+        ]);
+
+        assert_eq!(modifiers.to_string(), "+3 shield regen, -40% item use time");
+    }
+}
@@ -0,0 +1,85 @@
+use crate::prelude::*;
+
+/// Nanites for the first level of any suit upgrade. Each level costs this much
+/// more than the last.
+const BASE_COST: i32 = 30;
+
+/// Permanent improvements to the player's EV suit, bought at upgrade stations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SuitUpgrade {
+    Durability,
+    Armor,
+    ServoMotors,
+    TargetingAi,
+    ModSlots,
+}
+
+impl SuitUpgrade {
+    pub const ALL: [SuitUpgrade; 5] = [
+        Self::Durability,
+        Self::Armor,
+        Self::ServoMotors,
+        Self::TargetingAi,
+        Self::ModSlots,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Durability => "Reinforced plating",
+            Self::Armor => "Armor",
+            Self::ServoMotors => "Servo motors",
+            Self::TargetingAi => "Targeting AI",
+            Self::ModSlots => "Mod slots",
+        }
+    }
+
+    /// What each level of the upgrade does
+    pub fn effect(self) -> &'static str {
+        match self {
+            Self::Durability => "+5 max durability",
+            Self::Armor => "+1 defense",
+            Self::ServoMotors => "+1 melee damage",
+            Self::TargetingAi => "+1 ranged damage",
+            Self::ModSlots => "+1 mod slot",
+        }
+    }
+
+    pub fn max_level(self) -> i32 {
+        match self {
+            Self::ModSlots => 2,
+            _ => 5,
+        }
+    }
+
+    /// The cost of buying the next level, if there is one
+    pub fn cost(self, level: i32) -> Option<i32> {
+        (level < self.max_level()).then(|| BASE_COST * (level + 1))
+    }
+}
+
+/// How many levels of each suit upgrade have been bought
+#[derive(Component, Default)]
+pub struct SuitUpgrades([i32; SuitUpgrade::ALL.len()]);
+
+impl SuitUpgrades {
+    pub fn level(&self, upgrade: SuitUpgrade) -> i32 {
+        self.0[upgrade as usize]
+    }
+
+    pub(super) fn level_up(&mut self, upgrade: SuitUpgrade) {
+        self.0[upgrade as usize] += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, test_case::test_case};
+
+    #[test_case(SuitUpgrade::Armor, 0 => Some(30); "first level")]
+    #[test_case(SuitUpgrade::Armor, 2 => Some(90); "costs escalate")]
+    #[test_case(SuitUpgrade::Armor, 5 => None; "fully upgraded")]
+    #[test_case(SuitUpgrade::ModSlots, 2 => None; "mod slots cap early")]
+    fn upgrade_costs(upgrade: SuitUpgrade, level: i32) -> Option<i32> {
+        upgrade.cost(level)
+    }
+}
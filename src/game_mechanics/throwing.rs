@@ -0,0 +1,160 @@
+use super::{BeingUsed, InInventory, Stack};
+use crate::prelude::*;
+
+/// How far anything but a grenade can be thrown
+const THROW_RANGE: i32 = 5;
+/// Damage dealt by something hitting a target that wasn't made to be thrown
+const IMPACT_DAMAGE: i32 = 2;
+
+/// Queued on the item, which is thrown by whoever has it in their inventory.
+#[derive(Component)]
+pub struct WantsToThrow(pub(super) Coordinate);
+
+/// Items with a ground effect, i.e. grenades, go off where they land.
+pub fn blast_radius(usable: Option<&Usable>) -> Option<i32> {
+    match usable {
+        Some(&Usable::OnGround { radius, .. }) => Some(radius),
+        _ => None,
+    }
+}
+
+/// Items made to be thrown, i.e. grenades, reach as far as their own range.
+/// A weapon's range is how far it shoots, not how far it can be thrown.
+pub fn throw_range(usable: Option<&Usable>) -> i32 {
+    match usable {
+        Some(&Usable::OnGround { range, .. }) => range,
+        _ => THROW_RANGE,
+    }
+}
+
+/// The raw damage a thrown item deals to anything it lands on
+pub fn thrown_damage(usable: Option<&Usable>, damage: Option<&DealsDamage>) -> i32 {
+    match (blast_radius(usable), damage) {
+        (Some(_), Some(DealsDamage(damage))) => damage.value(),
+        (Some(_), None) => 0,
+        (None, _) => IMPACT_DAMAGE,
+    }
+}
+
+pub struct ThrowSystem;
+
+impl<'a> System<'a> for ThrowSystem {
+    type SystemData = (
+        ReadExpect<'a, Map>,
+        Entities<'a>,
+        WriteStorage<'a, WantsToThrow>,
+        WriteStorage<'a, InInventory>,
+        WriteStorage<'a, Coordinate>,
+        WriteStorage<'a, BeingUsed>,
+        WriteStorage<'a, Durability>,
+        ReadStorage<'a, Usable>,
+        ReadStorage<'a, BlocksTile>,
+        WriteStorage<'a, Stack>,
+        ReadStorage<'a, Appearance>,
+        Write<'a, GameLog>,
+        Read<'a, LazyUpdate>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            map,
+            entities,
+            mut throw_intents,
+            mut in_inventories,
+            mut positions,
+            mut being_used,
+            mut durabilities,
+            usables,
+            blockers,
+            mut stacks,
+            names,
+            mut game_log,
+            lazy,
+        ): Self::SystemData,
+    ) {
+        for (item, &WantsToThrow(target_pos)) in (&entities, &throw_intents).join() {
+            // Only one item off the top of a stack is thrown
+            let stacked = stacks.get(item).is_some_and(|stack| stack.count() > 1);
+            let thrower = if stacked {
+                in_inventories.get(item).map(|&InInventory(owner)| owner)
+            } else {
+                in_inventories.remove(item).map(|InInventory(owner)| owner)
+            };
+            let Some(thrower) = thrower else {
+                continue;
+            };
+            let Some(&thrower_pos) = positions.get(thrower) else {
+                continue;
+            };
+
+            let landing = map.impact(thrower_pos, target_pos);
+
+            if let Some(radius) = blast_radius(usables.get(item)) {
+                let targets = map
+                    .area_of_effect(landing, radius)
+                    .into_iter()
+                    .flat_map(|pos| map[pos].iter())
+                    .collect();
+
+                let effect = BeingUsed {
+                    targets,
+                    origin: landing,
+                    user: thrower,
+                };
+                being_used.insert(item, effect).unwrap();
+
+                continue;
+            }
+
+            if let Some(target) = map[landing].entity(&blockers) {
+                if let Some(durability) = durabilities.get_mut(target) {
+                    let damage = durability.take_damage(IMPACT_DAMAGE);
+                    if let (Some(item_name), Some(target_name)) =
+                        (names.get(item), names.get(target))
+                    {
+                        game_log.damage(item_name, target_name, damage);
+                    }
+                }
+            }
+
+            if let Some(stack) = stacks.get_mut(item).filter(|_| stacked) {
+                stack.split();
+
+                let builder = stack.builder;
+                lazy.exec_mut(move |world| {
+                    builder(world.create_entity()).with(landing).build();
+                });
+            } else {
+                positions.insert(item, landing).unwrap();
+            }
+        }
+
+        throw_intents.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, test_case::test_case};
+
+    const GRENADE: Usable = Usable::OnGround {
+        range: 6,
+        radius: 2,
+    };
+
+    #[test_case(Some(GRENADE), Some(8) => 8; "grenades explode")]
+    #[test_case(Some(GRENADE), None => 0; "harmless grenades")]
+    #[test_case(None, Some(8) => IMPACT_DAMAGE; "weapons only hit with their weight")]
+    #[test_case(None, None => IMPACT_DAMAGE; "junk")]
+    fn damage_on_landing(usable: Option<Usable>, damage: Option<i32>) -> i32 {
+        thrown_damage(usable.as_ref(), damage.map(DealsDamage::new).as_ref())
+    }
+
+    #[test_case(Some(GRENADE) => 6; "grenades")]
+    #[test_case(Some(Usable::OnTarget { range: 6 }) => THROW_RANGE; "ranged weapons")]
+    #[test_case(None => THROW_RANGE; "junk")]
+    fn range(usable: Option<Usable>) -> i32 {
+        throw_range(usable.as_ref())
+    }
+}
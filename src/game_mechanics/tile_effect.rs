@@ -0,0 +1,131 @@
+use super::BeingUsed;
+use crate::{entity::SpawnBuilder, prelude::*};
+
+/// Lingers on a tile for a number of turns, affecting whatever stands in it
+/// on each of its own turns.
+#[derive(Component)]
+pub struct TileEffect {
+    turns_left: i32,
+}
+
+impl TileEffect {
+    pub fn new(turns: i32) -> Self {
+        Self { turns_left: turns }
+    }
+}
+
+/// Percent chance each turn to spread a copy of the effect to each neighbouring
+/// tile. Copies only last as long as the original has left, so spreading
+/// effects burn themselves out.
+#[derive(Component)]
+pub struct Spreads {
+    pub chance: i32,
+    pub builder: SpawnBuilder,
+}
+
+/// Spawns a tile effect on every tile an item's effect reaches, e.g. the fire
+/// left behind by an incendiary grenade.
+#[derive(Component)]
+pub struct LeavesTileEffect(pub SpawnBuilder);
+
+/// Spawns a tile effect where a monster dies, e.g. the toxic gas hatchlings
+/// burst into.
+#[derive(Component)]
+pub struct DeathEffect(pub SpawnBuilder);
+
+pub struct TileEffectSystem;
+
+impl<'a> System<'a> for TileEffectSystem {
+    type SystemData = (
+        ReadExpect<'a, Map>,
+        Entities<'a>,
+        InitiativeData<'a>,
+        WriteStorage<'a, TileEffect>,
+        WriteStorage<'a, BeingUsed>,
+        ReadStorage<'a, Spreads>,
+        ReadStorage<'a, Durability>,
+        ReadStorage<'a, Coordinate>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        Read<'a, LazyUpdate>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            map,
+            entities,
+            mut initiative_data,
+            mut tile_effects,
+            mut being_used,
+            spreads,
+            durabilities,
+            positions,
+            mut rng,
+            lazy,
+        ): Self::SystemData,
+    ) {
+        let mut had_initiative: SmallVec<[Entity; 4]> = SmallVec::new();
+        let mut spreading = Vec::new();
+
+        for (entity, tile_effect, &pos, spread, _) in (
+            &entities,
+            &mut tile_effects,
+            &positions,
+            spreads.maybe(),
+            initiative_data.has_initiative(),
+        )
+            .join()
+        {
+            had_initiative.push(entity);
+
+            if tile_effect.turns_left <= 0 {
+                entities.delete(entity).unwrap();
+                continue;
+            }
+            tile_effect.turns_left -= 1;
+
+            let targets: SmallVec<_> = map[pos]
+                .iter()
+                .filter(|&target| durabilities.contains(target))
+                .collect();
+
+            if !targets.is_empty() {
+                let effect = BeingUsed {
+                    targets,
+                    origin: pos,
+                    user: entity,
+                };
+                being_used.insert(entity, effect).unwrap();
+            }
+
+            if let Some(spread) = spread {
+                spreading.push((pos, tile_effect.turns_left, spread));
+            }
+        }
+
+        for (pos, turns_left, &Spreads { chance, builder }) in spreading {
+            if turns_left <= 0 {
+                continue;
+            }
+
+            for neighbor in pos.neighbors() {
+                let open = map.in_bounds(neighbor)
+                    && !map[neighbor].is_opaque()
+                    && !map[neighbor].iter().any(|e| tile_effects.contains(e));
+
+                if open && rng.range(0, 100) < chance {
+                    lazy.exec_mut(move |world| {
+                        builder(world.create_entity())
+                            .with(neighbor)
+                            .with(TileEffect::new(turns_left))
+                            .build();
+                    });
+                }
+            }
+        }
+
+        for entity in had_initiative {
+            initiative_data.spend_turn(entity);
+        }
+    }
+}
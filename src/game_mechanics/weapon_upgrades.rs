@@ -0,0 +1,109 @@
+use super::{Charges, RecycleValue};
+use crate::prelude::*;
+
+pub(super) const WEAPON_UPGRADE_COST: i32 = 25;
+const WEAPON_UPGRADE_DAMAGE: i32 = 1;
+/// Extra capacity per upgrade for weapons with charges
+const WEAPON_UPGRADE_CHARGES: i32 = 2;
+/// How much of the upgrade cost is added to the weapon's recycle value
+const UPGRADE_RECYCLE_PERCENT: i32 = 40;
+/// Percent chance for a weapon to spawn with each further upgrade level
+const PRE_UPGRADE_CHANCE: i32 = 15;
+const MAX_PRE_UPGRADE_LEVEL: i32 = 3;
+
+/// How many times a weapon has been upgraded
+#[derive(Component, Default)]
+pub struct UpgradeLevel(pub i32);
+
+/// Applies one level of upgrades to a weapon
+pub(super) fn level_up_weapon(
+    UpgradeLevel(level): &mut UpgradeLevel,
+    DealsDamage(damage): &mut DealsDamage,
+    charges: Option<&mut Charges>,
+    recycle_value: Option<&mut RecycleValue>,
+    appearance: Option<&mut Appearance>,
+) {
+    *level += 1;
+    damage.increase_base(WEAPON_UPGRADE_DAMAGE);
+
+    if let Some(charges) = charges {
+        charges.increase_max(WEAPON_UPGRADE_CHARGES);
+    }
+
+    if let Some(RecycleValue(value)) = recycle_value {
+        *value += WEAPON_UPGRADE_COST * UPGRADE_RECYCLE_PERCENT / 100;
+    }
+
+    if let Some(appearance) = appearance {
+        appearance.set_upgrade_level(*level);
+    }
+}
+
+/// Gives weapons on the map, whether lying around, carried or stored, a small
+/// chance to already be upgraded. Runs before the player gets their gear.
+pub fn roll_weapon_upgrades(world: &mut World) {
+    let (
+        mut rng,
+        mut upgrade_levels,
+        mut deals_damage,
+        mut charges,
+        mut recycle_values,
+        mut appearances,
+    ) = world.system_data::<(
+        WriteExpect<RandomNumberGenerator>,
+        WriteStorage<UpgradeLevel>,
+        WriteStorage<DealsDamage>,
+        WriteStorage<Charges>,
+        WriteStorage<RecycleValue>,
+        WriteStorage<Appearance>,
+    )>();
+
+    for (level, damage, mut charges, mut recycle_value, mut appearance) in (
+        &mut upgrade_levels,
+        &mut deals_damage,
+        (&mut charges).maybe(),
+        (&mut recycle_values).maybe(),
+        (&mut appearances).maybe(),
+    )
+        .join()
+    {
+        while level.0 < MAX_PRE_UPGRADE_LEVEL && rng.range(0, 100) < PRE_UPGRADE_CHANCE {
+            level_up_weapon(
+                level,
+                damage,
+                charges.as_deref_mut(),
+                recycle_value.as_deref_mut(),
+                appearance.as_deref_mut(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn upgrades_improve_the_whole_weapon() {
+        let mut level = UpgradeLevel::default();
+        let mut damage = DealsDamage::new(6);
+        let mut charges = Charges::new(6);
+        let mut recycle_value = RecycleValue(12);
+        let mut appearance = Appearance::item("Plasma Cutter", '¬', CYAN);
+
+        for _ in 0..2 {
+            level_up_weapon(
+                &mut level,
+                &mut damage,
+                Some(&mut charges),
+                Some(&mut recycle_value),
+                Some(&mut appearance),
+            );
+        }
+
+        assert_eq!(damage.0.value(), 8);
+        assert_eq!(charges.max(), 10);
+        assert_eq!(recycle_value.0, 32);
+        assert_eq!(appearance.to_string(), "Plasma Cutter +2");
+    }
+}
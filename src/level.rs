@@ -1,5 +1,6 @@
 use crate::{
-    entity::SPAWN_TABLE,
+    entity::{CONTAINERS, HAZARDS, SPAWN_TABLE, STARTING_GEAR, STATIONS},
+    game_mechanics::{fill_starting_inventories, give_equipped, roll_weapon_upgrades},
     map::DeckBuilder,
     prelude::*,
     ui::{MAP_HEIGHT, MAP_WIDTH},
@@ -13,9 +14,16 @@ pub fn build_level(world: &mut World) {
             .with_engines()
             .with_walls(rng)
             .with_spawns(rng, &SPAWN_TABLE)
+            .with_safe_area(rng, &STATIONS)
+            .with_stations(rng, &STATIONS)
+            .with_hazards(rng, &HAZARDS)
+            .with_containers(rng, &CONTAINERS)
     }
     .spawn(world);
 
+    fill_starting_inventories(world);
+    roll_weapon_upgrades(world);
+
     // map.reveal();
 
     let player_entity = {
@@ -27,6 +35,10 @@ pub fn build_level(world: &mut World) {
 
     log::debug!("Player is {player_entity:?}");
 
+    for gear in STARTING_GEAR {
+        give_equipped(world, player_entity, gear);
+    }
+
     world.insert(map);
     world.insert(player_entity);
 }
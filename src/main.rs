@@ -16,12 +16,14 @@ mod prelude {
 
     pub use crate::{
         engine::RunState,
-        entity::{Item, Monster, Player},
+        entity::{Corpse, Description, Item, Monster, Player},
         game_mechanics::{
-            DealsDamage, Durability, EffectUsage, Initiative, InitiativeData, Intents, Inventory,
-            ProvidesHealing, Usable, Viewshed,
+            Charges, DamageBonus, DealsDamage, Durability, EffectUsage, EquipmentSlot, Equippable,
+            Initiative, InitiativeData, Intents, Inventory, Knockback, Loadout, ModSlots, Modifier,
+            NaniteCache, Nanites, ProvidesHealing, RecycleValue, RestoresCharge, Stat, StatKind,
+            StatModifiers, Station, SuitUpgrades, Usable, Viewshed,
         },
-        map::{BlocksTile, Coordinate, Direction, Map},
+        map::{BlocksSight, BlocksTile, Coordinate, Direction, Map},
         targeting::{Target, Targeting, TargetingReticule},
         ui::{Appearance, GameLog},
     };
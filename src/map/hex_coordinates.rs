@@ -78,6 +78,23 @@ impl Coordinate {
             .line_to_with_edge_detection_iter(other.into())
             .map(|(c1, c2)| (c1.into(), c2.into()))
     }
+
+    /// The cardinal direction which most closely points towards `other`.
+    ///
+    /// Returns `None` if both coordinates are the same.
+    pub fn direction_to(self, other: Self) -> Option<Direction> {
+        (self != other).then(|| {
+            Direction::ALL
+                .into_iter()
+                .min_by_key(|&dir| (self + dir).distance(other))
+                .unwrap()
+        })
+    }
+
+    /// Walk `distance` steps in a straight line
+    pub fn step(self, direction: Direction, distance: i32) -> Self {
+        (0..distance).fold(self, |coord, _| coord + direction)
+    }
 }
 
 impl From<Coordinate> for Point {
@@ -133,6 +150,10 @@ pub enum Direction {
     NorthWest,
 }
 
+impl Direction {
+    pub const ALL: [Direction; 6] = [North, NorthEast, SouthEast, South, SouthWest, NorthWest];
+}
+
 impl Add<Direction> for Coordinate {
     type Output = Coordinate;
 
@@ -180,4 +201,13 @@ mod test {
     fn adding_directions(direction: Direction) -> Coordinate {
         Coordinate::new(0, 0) + direction
     }
+
+    #[test_case(Coordinate::new( 0, -3) => Some(North);     "straight north")]
+    #[test_case(Coordinate::new( 3,  0) => Some(SouthEast); "straight south east")]
+    #[test_case(Coordinate::new(-1,  3) => Some(South);     "mostly south")]
+    #[test_case(Coordinate::new(-3,  1) => Some(SouthWest); "mostly south west")]
+    #[test_case(Coordinate::new( 0,  0) => None;            "same coordinate")]
+    fn finding_direction_to(other: Coordinate) -> Option<Direction> {
+        Coordinate::new(0, 0).direction_to(other)
+    }
 }
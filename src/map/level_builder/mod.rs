@@ -8,19 +8,35 @@ use crate::{
 };
 use std::collections::HashMap;
 
+/// Percent chance for each kind of station to appear on a deck
+const STATION_CHANCE: i32 = 25;
+const HAZARD_DICE: DiceType = DiceType {
+    n_dice: 1,
+    die_type: 4,
+    bonus: 0,
+};
+const CONTAINER_DICE: DiceType = DiceType {
+    n_dice: 2,
+    die_type: 3,
+    bonus: 0,
+};
+
 pub struct DeckBuilder {
     map: Map,
     spawns: HashMap<Coordinate, SpawnBuilder>,
+    /// Where the player starts the deck
+    start: Coordinate,
 }
 
 impl DeckBuilder {
     pub fn new(width: i32, height: i32) -> Self {
         let map = template::empty_deck(width, height);
 
+        let start = Coordinate::from(Point::new(width / 2, height / 2));
         let mut spawns: HashMap<Coordinate, SpawnBuilder> = HashMap::new();
-        spawns.insert(Coordinate::from(Point::new(width / 2, height / 2)), player);
+        spawns.insert(start, player);
 
-        Self { map, spawns }
+        Self { map, spawns, start }
     }
 
     pub fn with_engines(mut self) -> Self {
@@ -49,6 +65,100 @@ impl DeckBuilder {
         self
     }
 
+    /// The deck ends in a safe area with one of every kind of station. It's the
+    /// region furthest from the player's start, and any monsters in it are
+    /// cleared out.
+    pub fn with_safe_area(
+        mut self,
+        rng: &mut RandomNumberGenerator,
+        stations: &[SpawnBuilder],
+    ) -> Self {
+        let start = self.start;
+        let safe_area = spawner::generate_regions(&self.map, rng)
+            .into_iter()
+            .filter(|region| region.len() >= stations.len())
+            .max_by_key(|region| region.iter().map(|&c| start.distance(c)).min());
+
+        if let Some(safe_area) = safe_area {
+            self.spawns.retain(|coord, _| !safe_area.contains(coord));
+
+            for &station in stations {
+                self.place_in(rng, &safe_area, station);
+            }
+        }
+
+        self
+    }
+
+    /// Stations have a chance to spawn anywhere on the map.
+    pub fn with_stations(
+        mut self,
+        rng: &mut RandomNumberGenerator,
+        stations: &[SpawnBuilder],
+    ) -> Self {
+        for &station in stations {
+            if rng.range(0, 100) < STATION_CHANCE {
+                self.place(rng, station);
+            }
+        }
+
+        self
+    }
+
+    /// Scatters a few hazards like electrified puddles around the deck.
+    pub fn with_hazards(
+        mut self,
+        rng: &mut RandomNumberGenerator,
+        hazards: &[SpawnBuilder],
+    ) -> Self {
+        for _ in 0..rng.roll(HAZARD_DICE) {
+            if let Some(&hazard) = rng.random_slice_entry(hazards) {
+                self.place(rng, hazard);
+            }
+        }
+
+        self
+    }
+
+    /// Leaves lockers, crates and caches lying around for the player to loot.
+    pub fn with_containers(
+        mut self,
+        rng: &mut RandomNumberGenerator,
+        containers: &[SpawnBuilder],
+    ) -> Self {
+        for _ in 0..rng.roll(CONTAINER_DICE) {
+            if let Some(&container) = rng.random_slice_entry(containers) {
+                self.place(rng, container);
+            }
+        }
+
+        self
+    }
+
+    /// Spawns something on a random open tile
+    fn place(&mut self, rng: &mut RandomNumberGenerator, builder: SpawnBuilder) {
+        let tiles: Vec<_> = self.map.iter().collect();
+        self.place_in(rng, &tiles, builder);
+    }
+
+    /// Spawns something on a random open tile out of the given ones
+    fn place_in(
+        &mut self,
+        rng: &mut RandomNumberGenerator,
+        tiles: &[Coordinate],
+        builder: SpawnBuilder,
+    ) {
+        let open_tiles: Vec<_> = tiles
+            .iter()
+            .copied()
+            .filter(|&c| !self.map[c].is_blocked() && !self.spawns.contains_key(&c))
+            .collect();
+
+        if let Some(&coord) = rng.random_slice_entry(&open_tiles) {
+            self.spawns.insert(coord, builder);
+        }
+    }
+
     pub fn spawn(self, world: &mut World) -> Map {
         for (coord, builder) in self.spawns {
             builder(world.create_entity()).with(coord).build();
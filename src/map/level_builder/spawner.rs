@@ -21,7 +21,7 @@ pub fn generate_regions(map: &Map, rng: &mut RandomNumberGenerator) -> Vec<Vec<C
         noise_regions.entry(region_id).or_default().push(c);
     }
 
-    noise_regions.into_iter().map(|(_, v)| v).collect()
+    noise_regions.into_values().collect()
 }
 
 pub fn spawn_region(
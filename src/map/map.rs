@@ -57,7 +57,7 @@ impl Map {
             for (c1, c2) in origin.fat_line_to(edge) {
                 fov.extend([c1, c2]);
 
-                if self[c1].is_opaque() && self[c2].is_opaque() {
+                if self[c1].blocks_sight() && self[c2].blocks_sight() {
                     break;
                 }
             }
@@ -66,6 +66,39 @@ impl Map {
         fov
     }
 
+    /// The path a projectile takes from `origin` towards `target`.
+    ///
+    /// The path stops short of walls, and ends early on the first tile which
+    /// is blocked by something.
+    pub fn line_of_fire(&self, origin: Coordinate, target: Coordinate) -> Vec<Coordinate> {
+        let mut path = Vec::new();
+
+        for (c1, c2) in origin.fat_line_to(target).skip(1) {
+            let coord = if self[c1].is_opaque() { c2 } else { c1 };
+
+            if !self.in_bounds(coord) || self[coord].is_opaque() {
+                break;
+            }
+
+            path.push(coord);
+
+            if self[coord].is_blocked() {
+                break;
+            }
+        }
+
+        path
+    }
+
+    /// Where something thrown from `origin` at `target` lands. It lands early
+    /// if something is in the way.
+    pub fn impact(&self, origin: Coordinate, target: Coordinate) -> Coordinate {
+        self.line_of_fire(origin, target)
+            .last()
+            .copied()
+            .unwrap_or(origin)
+    }
+
     pub fn area_of_effect(&self, origin: Coordinate, radius: i32) -> HashSet<Coordinate> {
         let mut aoe = self.field_of_view(origin, radius);
         aoe.retain(|&c| !self[c].is_opaque());
@@ -99,10 +132,8 @@ impl BaseMap for Map {
         Coordinate::from_index(idx, self.width)
             .neighbors()
             .into_iter()
-            .filter_map(|potential_exit| {
-                is_legal_move(self, potential_exit)
-                    .then(|| (potential_exit.to_index(self.width), 1.0))
-            })
+            .filter(|&potential_exit| is_legal_move(self, potential_exit))
+            .map(|potential_exit| (potential_exit.to_index(self.width), 1.0))
             .collect()
     }
 
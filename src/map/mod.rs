@@ -13,6 +13,14 @@ use crate::prelude::*;
 #[derive(Component)]
 pub struct BlocksTile;
 
+/// Blocks field of view through the tile, but not movement or projectiles.
+///
+/// Flagged so that fields of view can be updated when sight blockers appear
+/// or disappear.
+#[derive(Component, Default)]
+#[storage(FlaggedStorage)]
+pub struct BlocksSight;
+
 pub struct IndexMapSystem;
 
 impl<'a> System<'a> for IndexMapSystem {
@@ -21,9 +29,13 @@ impl<'a> System<'a> for IndexMapSystem {
         Entities<'a>,
         ReadStorage<'a, Coordinate>,
         ReadStorage<'a, BlocksTile>,
+        ReadStorage<'a, BlocksSight>,
     );
 
-    fn run(&mut self, (mut map, entities, coordinates, blockers): Self::SystemData) {
+    fn run(
+        &mut self,
+        (mut map, entities, coordinates, blockers, sight_blockers): Self::SystemData,
+    ) {
         map.tiles.iter_mut().for_each(|tile| tile.reset_index());
 
         for (entity, coord) in (&entities, &coordinates).join() {
@@ -31,6 +43,10 @@ impl<'a> System<'a> for IndexMapSystem {
                 map[*coord].block();
             }
 
+            if sight_blockers.contains(entity) {
+                map[*coord].obscure();
+            }
+
             map[*coord].add_entity(entity);
         }
     }
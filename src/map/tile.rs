@@ -9,6 +9,8 @@ enum TileType {
 pub struct Tile {
     tile_type: TileType,
     blocked: bool,
+    /// Something on the tile, like smoke, blocks sight through it
+    obscured: bool,
     revealed: bool,
     contents: Vec<Entity>,
 }
@@ -18,6 +20,7 @@ impl Default for Tile {
         Self {
             tile_type: TileType::Wall,
             blocked: true,
+            obscured: false,
             revealed: false,
             contents: Vec::new(),
         }
@@ -41,6 +44,10 @@ impl Tile {
         self.tile_type == TileType::Wall
     }
 
+    pub fn blocks_sight(&self) -> bool {
+        self.is_opaque() || self.obscured
+    }
+
     pub fn is_blocked(&self) -> bool {
         self.blocked
     }
@@ -53,8 +60,16 @@ impl Tile {
         self.iter().find(|&entity| storage.contains(entity))
     }
 
+    pub fn entities<'a, T: Component>(
+        &'a self,
+        storage: &'a ReadStorage<T>,
+    ) -> impl Iterator<Item = Entity> + 'a {
+        self.iter().filter(|&entity| storage.contains(entity))
+    }
+
     pub(super) fn reset_index(&mut self) {
         self.blocked = self.tile_type == TileType::Wall;
+        self.obscured = false;
         self.contents.clear();
     }
 
@@ -62,6 +77,10 @@ impl Tile {
         self.blocked = true;
     }
 
+    pub(super) fn obscure(&mut self) {
+        self.obscured = true;
+    }
+
     pub fn reveal(&mut self) {
         self.revealed = true;
     }
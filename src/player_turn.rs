@@ -1,6 +1,9 @@
-use crate::game_mechanics::is_legal_move;
+use crate::game_mechanics::{
+    blast_radius, carried_stack, is_legal_move, throw_range, Container, InInventory, Keycard,
+    Locked, NaniteCache, Quickbar, Stack, INVENTORY_CAPACITY,
+};
 use crate::prelude::*;
-use anyhow::{anyhow, ensure, Context, Result};
+use anyhow::{anyhow, bail, ensure, Context, Result};
 
 pub fn handle_input(ctx: &BTerm, world: &mut World) -> RunState {
     use {Direction::*, RunState::*, VirtualKeyCode::*};
@@ -11,10 +14,16 @@ pub fn handle_input(ctx: &BTerm, world: &mut World) -> RunState {
         if ctx.control {
             let index = letter_to_option(key);
             if index >= 0 {
-                return player_turn.use_item(index as usize);
+                return player_turn
+                    .inventory_item(index as usize)
+                    .and_then(|item| player_turn.use_item(item));
             }
         }
 
+        if let Some(slot) = Quickbar::slot_for_key(key) {
+            return player_turn.use_quickbar(slot);
+        }
+
         match key {
             // Movement keys
             Q => player_turn.attack_or_move(NorthWest),
@@ -26,8 +35,21 @@ pub fn handle_input(ctx: &BTerm, world: &mut World) -> RunState {
 
             Tab => player_turn.cycle_target(ctx.shift),
 
+            F => player_turn.fire_ranged_weapon(),
+
+            G if ctx.shift => Ok(ChooseItem(ItemAction::Drop)),
             G => player_turn.pick_up_item(),
 
+            T => Ok(ChooseItem(ItemAction::Throw)),
+
+            O => player_turn.interact(),
+
+            X => player_turn.examine(),
+
+            C => Ok(CharacterSheet),
+
+            I => Ok(InventoryScreen),
+
             Escape => Ok(Quitting),
 
             _ => Ok(AwaitingInput),
@@ -43,20 +65,45 @@ pub fn handle_input(ctx: &BTerm, world: &mut World) -> RunState {
     })
 }
 
+/// Something done to an item picked from the inventory menu
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ItemAction {
+    Drop,
+    Throw,
+}
+
+impl ItemAction {
+    pub fn prompt(self) -> &'static str {
+        match self {
+            Self::Drop => "Drop which item?",
+            Self::Throw => "Throw which item?",
+        }
+    }
+}
+
 #[derive(SystemData)]
 pub struct PlayerTurn<'a> {
     map: ReadExpect<'a, Map>,
     player: ReadExpect<'a, Entity>,
     inventory: Read<'a, Inventory>,
+    quickbar: Read<'a, Quickbar>,
     lazy: Read<'a, LazyUpdate>,
     intents: Intents<'a>,
     targeting: Targeting<'a>,
     effect_usage: EffectUsage<'a>,
     initiative_data: InitiativeData<'a>,
+    loadout: Loadout<'a>,
     entities: Entities<'a>,
     positions: ReadStorage<'a, Coordinate>,
     monsters: ReadStorage<'a, Monster>,
     items: ReadStorage<'a, Item>,
+    stations: ReadStorage<'a, Station>,
+    containers: ReadStorage<'a, Container>,
+    locks: ReadStorage<'a, Locked>,
+    keycards: ReadStorage<'a, Keycard>,
+    in_inventories: ReadStorage<'a, InInventory>,
+    stacks: ReadStorage<'a, Stack>,
+    nanite_caches: ReadStorage<'a, NaniteCache>,
     usables: ReadStorage<'a, Usable>,
     viewsheds: ReadStorage<'a, Viewshed>,
 }
@@ -68,10 +115,19 @@ impl<'a> PlayerTurn<'a> {
         let dest = pos + direction;
 
         if let Some(target) = self.map[dest].entity(&self.monsters) {
+            let weapon = self
+                .loadout
+                .melee_weapon(*self.player)
+                .unwrap_or(*self.player);
+
             self.targeting.set_target(*self.player, Some(target));
             self.effect_usage
-                .use_on_target(*self.player, *self.player, target)?;
+                .use_on_target(weapon, *self.player, target)?;
             self.initiative_data.spend_turn(*self.player);
+        } else if let Some(station) = self.map[dest].entity(&self.stations) {
+            return Ok(RunState::UseStation(station));
+        } else if let Some(container) = self.map[dest].entity(&self.containers) {
+            return self.open_container(container);
         } else {
             ensure!(is_legal_move(&self.map, dest), "Movement blocked");
 
@@ -83,28 +139,142 @@ impl<'a> PlayerTurn<'a> {
         Ok(RunState::Running)
     }
 
+    /// Uses the first station or container next to the player
+    pub fn interact(&mut self) -> Result<RunState> {
+        let pos = *self.positions.get(*self.player).unwrap();
+
+        for direction in Direction::ALL {
+            let tile = &self.map[pos + direction];
+
+            if let Some(station) = tile.entity(&self.stations) {
+                return Ok(RunState::UseStation(station));
+            }
+            if let Some(container) = tile.entity(&self.containers) {
+                return self.open_container(container);
+            }
+        }
+
+        bail!("nothing to interact with")
+    }
+
+    /// Looks inside an open container, otherwise spends a turn working on it
+    pub fn open_container(&mut self, container: Entity) -> Result<RunState> {
+        let state = self.containers.get(container).context("not a container")?;
+        if state.is_open() {
+            return Ok(RunState::UseContainer(container));
+        }
+
+        ensure!(
+            !self.locks.contains(container)
+                || self
+                    .inventory
+                    .items()
+                    .any(|item| self.keycards.contains(item)),
+            "locked, and you have no keycard"
+        );
+
+        self.intents.wants_to_open(*self.player, container);
+        self.initiative_data.spend_turn(*self.player);
+
+        Ok(RunState::Running)
+    }
+
+    /// Everything inside a container
+    pub fn contents(&self, container: Entity) -> Vec<Entity> {
+        (&self.entities, &self.in_inventories)
+            .join()
+            .filter(|(_, carried)| carried.owner() == container)
+            .map(|(item, _)| item)
+            .collect()
+    }
+
+    pub fn take_from_container(&mut self, items: &[Entity]) -> Result<RunState> {
+        ensure!(!items.is_empty(), "nothing to take");
+
+        self.pick_up(items)
+    }
+
+    pub fn put_in_container(&mut self, container: Entity, item: Entity) -> Result<RunState> {
+        ensure!(
+            self.contents(container).len() < INVENTORY_CAPACITY,
+            "no room left inside"
+        );
+
+        if self.loadout.is_equipped(item) {
+            self.intents.wants_to_unequip(item);
+        }
+        self.intents.wants_to_pick_up(container, item);
+        self.initiative_data.spend_turn(*self.player);
+
+        Ok(RunState::Running)
+    }
+
+    pub fn fire_ranged_weapon(&mut self) -> Result<RunState> {
+        let weapon = self
+            .loadout
+            .ranged_weapon(*self.player)
+            .context("no ranged weapon equipped")?;
+        let Target(target) = *self.targeting.get(*self.player).context("no target")?;
+
+        self.effect_usage
+            .use_on_target(weapon, *self.player, target)?;
+        self.initiative_data.spend_turn(*self.player);
+
+        Ok(RunState::Running)
+    }
+
     pub fn pick_up_item(&mut self) -> Result<RunState> {
+        match *self.items_here() {
+            [] => bail!("nothing to pick up"),
+            [item] => self.pick_up(&[item]),
+            _ => Ok(RunState::ChoosePickup),
+        }
+    }
+
+    /// Everything lying on the player's tile
+    pub fn items_here(&self) -> Vec<Entity> {
         let pos = *self.positions.get(*self.player).unwrap();
-        let item = self.map[pos]
-            .entity(&self.items)
-            .context("nothing to pick up")?;
 
-        self.intents.wants_to_pick_up(*self.player, item);
+        self.map[pos].entities(&self.items).collect()
+    }
+
+    pub fn pick_up(&mut self, items: &[Entity]) -> Result<RunState> {
+        let has_room = self.inventory.count() < INVENTORY_CAPACITY;
+        ensure!(
+            has_room || items.iter().any(|&item| self.fits_when_full(item)),
+            "inventory full"
+        );
+
+        for &item in items {
+            self.intents.wants_to_pick_up(*self.player, item);
+        }
         self.initiative_data.spend_turn(*self.player);
 
         Ok(RunState::Running)
     }
 
-    pub fn use_item(&mut self, index: usize) -> Result<RunState> {
-        let item = *self.inventory.0.get(index).with_context(|| {
-            let label = (b'A' + index as u8) as char;
-            anyhow!("no item \"{label}\"")
-        })?;
+    /// Nanites and items joining a carried stack don't take up a letter
+    fn fits_when_full(&self, item: Entity) -> bool {
+        self.nanite_caches.contains(item)
+            || carried_stack(
+                item,
+                *self.player,
+                &self.entities,
+                &self.in_inventories,
+                &self.stacks,
+            )
+            .is_some()
+    }
+
+    pub fn use_item(&mut self, item: Entity) -> Result<RunState> {
+        if self.loadout.is_equippable(item) {
+            return self.toggle_equipped(item);
+        }
 
         match *self.usables.get(item).context("not usable")? {
             Usable::OnSelf => {
                 self.effect_usage.use_on_self(item, *self.player)?;
-                self.initiative_data.spend_turn(*self.player);
+                self.initiative_data.spend_item_turn(*self.player);
 
                 Ok(RunState::Running)
             }
@@ -113,14 +283,14 @@ impl<'a> PlayerTurn<'a> {
 
                 self.effect_usage
                     .use_on_target(item, *self.player, target)?;
-                self.initiative_data.spend_turn(*self.player);
+                self.initiative_data.spend_item_turn(*self.player);
 
                 Ok(RunState::Running)
             }
             Usable::OnGround { range, radius } => {
                 let player_pos = *self.positions.get(*self.player).unwrap();
                 let targeting_reticule =
-                    TargetingReticule::new(player_pos, range, radius, &self.map);
+                    TargetingReticule::new(item, player_pos, range, radius, &self.map);
 
                 self.lazy.exec_mut(|world| world.insert(targeting_reticule));
 
@@ -129,6 +299,67 @@ impl<'a> PlayerTurn<'a> {
         }
     }
 
+    pub fn use_quickbar(&mut self, slot: usize) -> Result<RunState> {
+        let item = self
+            .quickbar
+            .get(slot)
+            .with_context(|| anyhow!("nothing bound to \"{}\"", Quickbar::label(slot)))?;
+
+        self.use_item(item)
+    }
+
+    pub fn toggle_equipped(&mut self, item: Entity) -> Result<RunState> {
+        ensure!(self.loadout.is_equippable(item), "can't be equipped");
+
+        if self.loadout.is_equipped(item) {
+            self.intents.wants_to_unequip(item);
+        } else {
+            self.intents.wants_to_equip(*self.player, item);
+        }
+        self.initiative_data.spend_turn(*self.player);
+
+        Ok(RunState::Running)
+    }
+
+    pub fn act_on_item(&mut self, action: ItemAction, item: Entity) -> Result<RunState> {
+        match action {
+            ItemAction::Drop => self.drop_item(item),
+            ItemAction::Throw => self.throw_item(item),
+        }
+    }
+
+    pub fn drop_item(&mut self, item: Entity) -> Result<RunState> {
+        if self.loadout.is_equipped(item) {
+            self.intents.wants_to_unequip(item);
+        }
+        self.intents.wants_to_drop(item);
+        self.initiative_data.spend_turn(*self.player);
+
+        Ok(RunState::Running)
+    }
+
+    pub fn throw_item(&mut self, item: Entity) -> Result<RunState> {
+        let player_pos = *self.positions.get(*self.player).unwrap();
+        let usable = self.usables.get(item);
+        let range = throw_range(usable);
+        let radius = blast_radius(usable).unwrap_or(0);
+        let targeting_reticule = TargetingReticule::new(item, player_pos, range, radius, &self.map);
+
+        self.lazy.exec_mut(|world| world.insert(targeting_reticule));
+
+        Ok(RunState::ThrowItem(item))
+    }
+
+    pub fn examine(&mut self) -> Result<RunState> {
+        let player_pos = *self.positions.get(*self.player).unwrap();
+        let range = self.viewsheds.get(*self.player).unwrap().range();
+        let targeting_reticule = TargetingReticule::examine(player_pos, range, &self.map);
+
+        self.lazy.exec_mut(|world| world.insert(targeting_reticule));
+
+        Ok(RunState::Examine)
+    }
+
     pub fn cycle_target(&mut self, rev: bool) -> Result<RunState> {
         let viewshed = self.viewsheds.get(*self.player).unwrap();
 
@@ -146,4 +377,10 @@ impl<'a> PlayerTurn<'a> {
 
         Ok(RunState::AwaitingInput)
     }
+
+    pub fn inventory_item(&self, index: usize) -> Result<Entity> {
+        self.inventory
+            .get(index)
+            .with_context(|| anyhow!("no item \"{}\"", Inventory::label(index)))
+    }
 }
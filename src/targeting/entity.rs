@@ -76,7 +76,7 @@ impl<'a> System<'a> for ClearTargetSystem {
             .filter(|(_, target, vs)| {
                 positions
                     .get(target.0)
-                    .map_or(true, |&coord| !vs.is_visible(coord))
+                    .is_none_or(|&coord| !vs.is_visible(coord))
             })
             .map(|(entity, _, _)| entity)
             .collect();
@@ -1,47 +1,213 @@
-use crate::prelude::*;
+use crate::{prelude::*, ui};
 use std::{collections::HashSet, ops::ControlFlow};
 
 pub fn use_ground_effect(effect: Entity, ctx: &BTerm, world: &mut World) -> RunState {
+    aim(
+        effect,
+        ctx,
+        world,
+        RunState::TargetGround(effect),
+        use_on_ground,
+    )
+}
+
+pub fn throw_item(item: Entity, ctx: &BTerm, world: &mut World) -> RunState {
+    aim(item, ctx, world, RunState::ThrowItem(item), throw)
+}
+
+/// Moves the reticule until the player picks a target, then releases the
+/// item at it, asking first if the player would be hit.
+fn aim(
+    item: Entity,
+    ctx: &BTerm,
+    world: &mut World,
+    aiming: RunState,
+    release: fn(Entity, Coordinate, &mut World) -> RunState,
+) -> RunState {
+    if world.fetch::<TargetingReticule>().confirming {
+        return confirm_self_damage(item, ctx, world, aiming, release);
+    }
+
     let res = world.fetch_mut::<TargetingReticule>().handle_input(ctx);
 
     match res {
-        ControlFlow::Continue(()) => RunState::TargetGround(effect),
+        ControlFlow::Continue(()) => aiming,
+        ControlFlow::Break(Some(target_pos)) => {
+            if hits_player(world) {
+                world.fetch_mut::<TargetingReticule>().confirming = true;
+                return aiming;
+            }
+
+            world.remove::<TargetingReticule>();
+            release(item, target_pos, world)
+        }
+        ControlFlow::Break(None) => {
+            world.remove::<TargetingReticule>();
+
+            RunState::AwaitingInput
+        }
+    }
+}
+
+fn confirm_self_damage(
+    item: Entity,
+    ctx: &BTerm,
+    world: &mut World,
+    aiming: RunState,
+    release: fn(Entity, Coordinate, &mut World) -> RunState,
+) -> RunState {
+    use VirtualKeyCode::*;
+
+    ui::confirm_self_damage();
+
+    match ctx.key {
+        Some(Y) => {
+            let target_pos = world.fetch::<TargetingReticule>().cursor;
+            world.remove::<TargetingReticule>();
+            release(item, target_pos, world)
+        }
+        Some(N | Escape) => {
+            world.fetch_mut::<TargetingReticule>().confirming = false;
+            aiming
+        }
+        _ => aiming,
+    }
+}
+
+/// Whether the player would be caught in their own blast. Harmless blasts
+/// count too, since they can still do things like strip shields.
+fn hits_player(world: &World) -> bool {
+    let player = *world.fetch::<Entity>();
+    let positions = world.read_storage::<Coordinate>();
+
+    positions.get(player).is_some_and(|pos| {
+        world
+            .fetch::<TargetingReticule>()
+            .blast_area(&world.fetch::<Map>())
+            .contains(pos)
+    })
+}
+
+fn use_on_ground(effect: Entity, target_pos: Coordinate, world: &mut World) -> RunState {
+    let player = *world.fetch::<Entity>();
+    let mut effect_usage = EffectUsage::fetch(world);
+    let mut initiative_data = InitiativeData::fetch(world);
+
+    effect_usage
+        .use_on_ground(effect, player, target_pos)
+        .unwrap();
+    initiative_data.spend_item_turn(player);
+
+    RunState::Running
+}
+
+fn throw(item: Entity, target_pos: Coordinate, world: &mut World) -> RunState {
+    let player = *world.fetch::<Entity>();
+    let loadout = Loadout::fetch(world);
+    let mut intents = Intents::fetch(world);
+    let mut initiative_data = InitiativeData::fetch(world);
+
+    if loadout.is_equipped(item) {
+        intents.wants_to_unequip(item);
+    }
+    intents.wants_to_throw(item, target_pos);
+    initiative_data.spend_item_turn(player);
+
+    RunState::Running
+}
+
+pub fn examine_tile(ctx: &BTerm, world: &mut World) -> RunState {
+    let res = world.fetch_mut::<TargetingReticule>().handle_input(ctx);
+
+    match res {
+        ControlFlow::Continue(()) => RunState::Examine,
         ControlFlow::Break(target) => {
             world.remove::<TargetingReticule>();
 
             if let Some(target_pos) = target {
-                let player = *world.fetch::<Entity>();
-                let mut effect_usage = EffectUsage::fetch(world);
-                let mut initiative_data = InitiativeData::fetch(world);
-
-                effect_usage
-                    .use_on_ground(effect, player, target_pos)
-                    .unwrap();
-                initiative_data.spend_turn(player);
-
-                RunState::Running
-            } else {
-                RunState::AwaitingInput
+                let map = world.fetch::<Map>();
+                let appearances = world.read_storage::<Appearance>();
+                let descriptions = world.read_storage::<Description>();
+                let durabilities = world.read_storage::<Durability>();
+                let modifiers = world.read_storage::<StatModifiers>();
+                let mut game_log = world.fetch_mut::<GameLog>();
+
+                let mut contents: Vec<_> = map[target_pos]
+                    .iter()
+                    .filter_map(|entity| Some((entity, appearances.get(entity)?)))
+                    .collect();
+                contents.sort_by_key(|(_, appearance)| -appearance.z_order());
+
+                if contents.is_empty() {
+                    game_log.examine_nothing();
+                }
+
+                for (entity, appearance) in contents {
+                    game_log.examine(
+                        appearance,
+                        descriptions.get(entity),
+                        durabilities.get(entity),
+                        modifiers.get(entity),
+                    );
+                }
             }
+
+            RunState::AwaitingInput
         }
     }
 }
 
 pub struct TargetingReticule {
+    pub origin: Coordinate,
     pub coordinates: HashSet<Coordinate>,
     pub cursor: Coordinate,
     pub aoe_radius: i32,
+    /// What's being aimed, as opposed to just looking around
+    pub effect: Option<Entity>,
+    /// Waiting for the player to confirm a throw which would hit themselves
+    pub confirming: bool,
 }
 
 impl TargetingReticule {
-    pub fn new(origin: Coordinate, range: i32, aoe_radius: i32, map: &Map) -> Self {
+    pub fn new(effect: Entity, origin: Coordinate, range: i32, aoe_radius: i32, map: &Map) -> Self {
         Self {
+            aoe_radius,
+            effect: Some(effect),
+            ..Self::examine(origin, range, map)
+        }
+    }
+
+    /// A single tile cursor for looking around without targeting anything
+    pub fn examine(origin: Coordinate, range: i32, map: &Map) -> Self {
+        Self {
+            origin,
             coordinates: map.area_of_effect(origin, range),
             cursor: origin,
-            aoe_radius,
+            aoe_radius: 0,
+            effect: None,
+            confirming: false,
         }
     }
 
+    pub fn is_aiming(&self) -> bool {
+        self.effect.is_some()
+    }
+
+    /// The path from the origin to where a projectile aimed at the cursor
+    /// would actually land
+    pub fn projectile_path(&self, map: &Map) -> Vec<Coordinate> {
+        map.line_of_fire(self.origin, self.cursor)
+    }
+
+    pub fn impact(&self, map: &Map) -> Coordinate {
+        map.impact(self.origin, self.cursor)
+    }
+
+    /// Every tile the effect would reach if used now
+    pub fn blast_area(&self, map: &Map) -> HashSet<Coordinate> {
+        map.area_of_effect(self.impact(map), self.aoe_radius)
+    }
+
     pub fn handle_input(&mut self, ctx: &BTerm) -> ControlFlow<Option<Coordinate>> {
         use {ControlFlow::*, Direction::*, VirtualKeyCode::*};
 
@@ -2,4 +2,4 @@ mod entity;
 mod map_tile;
 
 pub use entity::{ClearTargetSystem, Target, Targeting};
-pub use map_tile::{use_ground_effect, TargetingReticule};
+pub use map_tile::{examine_tile, throw_item, use_ground_effect, TargetingReticule};
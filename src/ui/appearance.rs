@@ -7,6 +7,8 @@ pub struct Appearance {
     pub(super) glyph: char,
     pub(super) color: ColorPair,
     pub(super) z_order: i32,
+    /// Shown after the name, e.g. "Plasma Cutter +2"
+    upgrade_level: i32,
 }
 
 impl Appearance {
@@ -16,6 +18,7 @@ impl Appearance {
             glyph: '@',
             color: ColorPair::new(YELLOW, RGBA::new()),
             z_order: 30,
+            upgrade_level: 0,
         }
     }
 
@@ -25,6 +28,7 @@ impl Appearance {
             glyph,
             color: ColorPair::new(color, RGBA::new()),
             z_order: 20,
+            upgrade_level: 0,
         }
     }
 
@@ -34,6 +38,48 @@ impl Appearance {
             glyph,
             color: ColorPair::new(color, RGBA::new()),
             z_order: 10,
+            upgrade_level: 0,
+        }
+    }
+
+    /// Fire, smoke and the like, drawn over items but under anyone standing in it
+    pub fn tile_effect(name: impl ToString, glyph: char, color: impl Into<RGBA>) -> Self {
+        Self {
+            name: name.to_string(),
+            glyph,
+            color: ColorPair::new(color, RGBA::new()),
+            z_order: 12,
+            upgrade_level: 0,
+        }
+    }
+
+    pub fn corpse(victim: &Appearance) -> Self {
+        Self {
+            name: format!("{victim} corpse"),
+            glyph: '%',
+            color: ColorPair::new(victim.color.fg.lerp(RGBA::named(BLACK), 0.5), RGBA::new()),
+            z_order: 5,
+            upgrade_level: 0,
+        }
+    }
+
+    pub fn station(name: impl ToString, color: impl Into<RGBA>) -> Self {
+        Self {
+            name: name.to_string(),
+            glyph: '■',
+            color: ColorPair::new(color, RGBA::new()),
+            z_order: 15,
+            upgrade_level: 0,
+        }
+    }
+
+    pub fn container(name: impl ToString, glyph: char, color: impl Into<RGBA>) -> Self {
+        Self {
+            name: name.to_string(),
+            glyph,
+            color: ColorPair::new(color, RGBA::new()),
+            z_order: 15,
+            upgrade_level: 0,
         }
     }
 
@@ -43,12 +89,27 @@ impl Appearance {
             glyph,
             color: ColorPair::new(color, BLACK),
             z_order: 0,
+            upgrade_level: 0,
         }
     }
+
+    pub fn z_order(&self) -> i32 {
+        self.z_order
+    }
+
+    pub fn set_upgrade_level(&mut self, level: i32) {
+        self.upgrade_level = level;
+    }
 }
 
 impl Display for Appearance {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(&self.name)
+        f.write_str(&self.name)?;
+
+        if self.upgrade_level > 0 {
+            write!(f, " +{}", self.upgrade_level)?;
+        }
+
+        Ok(())
     }
 }
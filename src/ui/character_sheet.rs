@@ -0,0 +1,94 @@
+use super::menu::Menu;
+use crate::{
+    game_mechanics::{SuitUpgrade, SuitUpgrades},
+    prelude::*,
+};
+
+#[derive(SystemData)]
+struct CharacterSheetData<'a> {
+    player: ReadExpect<'a, Entity>,
+    durabilities: ReadStorage<'a, Durability>,
+    initiatives: ReadStorage<'a, Initiative>,
+    damage_bonuses: ReadStorage<'a, DamageBonus>,
+    mod_slots: ReadStorage<'a, ModSlots>,
+    suit_upgrades: ReadStorage<'a, SuitUpgrades>,
+    appearances: ReadStorage<'a, Appearance>,
+    stat_modifiers: ReadStorage<'a, StatModifiers>,
+    loadout: Loadout<'a>,
+}
+
+/// Render the player's stats and suit upgrades until they close the sheet
+pub fn character_sheet(ctx: &BTerm, world: &mut World) -> RunState {
+    use {RunState::*, VirtualKeyCode::*};
+
+    let data = CharacterSheetData::fetch(world);
+    let player = *data.player;
+
+    let mut menu = Menu::new("EV Suit");
+
+    if let Some(durability) = data.durabilities.get(player) {
+        let (health, max_health) = durability.health();
+        menu = menu
+            .text(format!("Durability: {health}/{max_health}"))
+            .text(format!("Defense: {}", durability.defense()))
+            .text(format!("Regen: {}/turn", durability.health_regen()));
+
+        if let Some((shield, max_shield)) = durability.shield() {
+            let (shield_defense, shield_regen) = durability.shield_stats();
+            menu = menu
+                .text(format!("Shield: {shield}/{max_shield}"))
+                .text(format!("Shield defense: {shield_defense}"))
+                .text(format!("Shield regen: {shield_regen}/turn"));
+        }
+    }
+
+    if let Some(initiative) = data.initiatives.get(player) {
+        menu = menu
+            .text(format!("Turn delay: {}", initiative.speed.value()))
+            .text(format!(
+                "Item use time: {}%",
+                initiative.item_use_time.value()
+            ));
+    }
+
+    if let Some(bonus) = data.damage_bonuses.get(player) {
+        menu = menu
+            .text(format!("Melee damage: +{}", bonus.melee.value()))
+            .text(format!("Ranged damage: +{}", bonus.ranged.value()));
+    }
+
+    if let Some(&ModSlots(slots)) = data.mod_slots.get(player) {
+        let used = data.loadout.in_slot(player, EquipmentSlot::Mod).count();
+        menu = data
+            .loadout
+            .in_slot(player, EquipmentSlot::Mod)
+            .filter_map(|item| {
+                let appearance = data.appearances.get(item)?;
+                Some((appearance, data.stat_modifiers.get(item)?))
+            })
+            .fold(
+                menu.text(format!("Mod slots: {used}/{slots}")),
+                |menu, (appearance, modifiers)| menu.text(format!("  {appearance}: {modifiers}")),
+            );
+    }
+
+    if let Some(upgrades) = data.suit_upgrades.get(player) {
+        menu = SuitUpgrade::ALL
+            .into_iter()
+            .fold(menu.blank().text("Upgrades"), |menu, upgrade| {
+                menu.text(format!(
+                    "  {}: {}/{}",
+                    upgrade.label(),
+                    upgrades.level(upgrade),
+                    upgrade.max_level()
+                ))
+            });
+    }
+
+    menu.blank().option("Esc", "Close").draw();
+
+    match ctx.key {
+        Some(Escape | C) => AwaitingInput,
+        _ => CharacterSheet,
+    }
+}
@@ -0,0 +1,74 @@
+use super::menu::Menu;
+use crate::{game_mechanics::Stack, player_turn::PlayerTurn, prelude::*};
+use anyhow::Context;
+
+/// Render what's inside a container next to the player's inventory and move
+/// items between the two
+pub fn container_menu(container: Entity, ctx: &BTerm, world: &mut World) -> RunState {
+    use {RunState::*, VirtualKeyCode::*};
+
+    let mut player_turn = PlayerTurn::fetch(world);
+    let contents = player_turn.contents(container);
+
+    {
+        let inventory = world.fetch::<Inventory>();
+        let appearances = world.read_storage::<Appearance>();
+        let stacks = world.read_storage::<Stack>();
+        let name = |item: Entity| {
+            let appearance = appearances.get(item)?;
+
+            Some(match stacks.get(item).filter(|stack| stack.count() > 1) {
+                Some(stack) => format!("{appearance} x{}", stack.count()),
+                None => appearance.to_string(),
+            })
+        };
+
+        let title = appearances
+            .get(container)
+            .map_or_else(String::new, ToString::to_string);
+        let labels = (b'A'..=b'Z').map(|label| label as char);
+
+        let menu = contents
+            .iter()
+            .zip(labels)
+            .filter_map(|(&item, label)| Some((name(item)?, label)))
+            .fold(Menu::new(title).text("Inside:"), |menu, (name, label)| {
+                menu.option(label, name)
+            });
+
+        let menu = inventory
+            .labelled()
+            .filter_map(|(label, item)| Some((name(item)?, label)))
+            .fold(
+                menu.blank().text("Carried (Shift to put in):"),
+                |menu, (name, label)| menu.option(label, name),
+            );
+
+        menu.blank()
+            .option("Space", "Take all")
+            .option("Esc", "Close")
+            .draw();
+    }
+
+    let run_state = match ctx.key {
+        Some(Escape) => Ok(AwaitingInput),
+        Some(Space) => player_turn.take_from_container(&contents),
+        Some(key) if letter_to_option(key) >= 0 && ctx.shift => player_turn
+            .inventory_item(letter_to_option(key) as usize)
+            .and_then(|item| player_turn.put_in_container(container, item)),
+        Some(key) if letter_to_option(key) >= 0 => contents
+            .get(letter_to_option(key) as usize)
+            .context("no such item")
+            .and_then(|&item| player_turn.take_from_container(&[item])),
+        _ => Ok(UseContainer(container)),
+    };
+
+    drop(player_turn);
+    world.maintain();
+
+    run_state.unwrap_or_else(|reason| {
+        log::warn!("{reason}");
+
+        UseContainer(container)
+    })
+}
@@ -1,5 +1,5 @@
 use super::{FULL_PAINT, MAP_HEIGHT, MAP_WIDTH, SIDEBAR_WIDTH, TERM_HEIGHT};
-use crate::prelude::*;
+use crate::{game_mechanics::SuitUpgrade, prelude::*};
 
 const LOG_WIDTH: i32 = MAP_WIDTH - 2;
 const LOG_HEIGHT: i32 = TERM_HEIGHT - MAP_HEIGHT - 5;
@@ -30,6 +30,20 @@ impl GameLog {
         });
     }
 
+    pub fn shield_stripped(&mut self, source: &Appearance, target: &Appearance, amount: i32) {
+        log::info!("{source} strips {amount} shield from {target}");
+        self.log(|text| {
+            text.append(&format!("{source} strips {amount} shield from {target}"));
+        });
+    }
+
+    pub fn chilled(&mut self, source: &Appearance, target: &Appearance) {
+        log::info!("{source} slows {target}");
+        self.log(|text| {
+            text.append(&format!("{source} slows {target}"));
+        });
+    }
+
     pub fn healing(&mut self, source: &Appearance, target: &Appearance, amount: i32) {
         log::info!("{source} heals {amount} damage for {target}");
         self.log(|text| {
@@ -37,6 +51,36 @@ impl GameLog {
         });
     }
 
+    pub fn collision(&mut self, victim: &Appearance, damage: i32) {
+        log::info!("{victim} is slammed into an obstacle for {damage} damage");
+        self.log(|text| {
+            text.append(&format!(
+                "{victim} is slammed into an obstacle for {damage} damage"
+            ));
+        });
+    }
+
+    pub fn weapon_recharged(&mut self, source: &Appearance, weapon: &Appearance, amount: i32) {
+        log::info!("{source} restores {amount} charges to {weapon}");
+        self.log(|text| {
+            text.append(&format!("{source} restores {amount} charges to {weapon}"));
+        });
+    }
+
+    pub fn shield_recharged(&mut self, source: &Appearance, target: &Appearance, amount: i32) {
+        log::info!("{source} recharges {target}'s shield by {amount}");
+        self.log(|text| {
+            text.append(&format!("{source} recharges {target}'s shield by {amount}"));
+        });
+    }
+
+    pub fn item_recharged(&mut self, item: &Appearance, amount: i32) {
+        log::info!("{item} was recharged by {amount}");
+        self.log(|text| {
+            text.append(&format!("{item} was recharged by {amount}"));
+        });
+    }
+
     pub fn player_pickup(&mut self, item: &Appearance) {
         log::info!("You picked up {item}");
         self.log(|text| {
@@ -44,11 +88,204 @@ impl GameLog {
         });
     }
 
-    pub fn player_death(&mut self) {
+    pub fn pickup(&mut self, recipient: &Appearance, item: &Appearance) {
+        log::info!("{recipient} picks up {item}");
+        self.log(|text| {
+            text.append(&format!("{recipient} picks up {item}"));
+        });
+    }
+
+    pub fn items_here(&mut self, items: &[&Appearance]) {
+        let items = items
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        log::info!("You see here: {items}");
+        self.log(|text| {
+            text.append(&format!("You see here: {items}"));
+        });
+    }
+
+    pub fn stored(&mut self, item: &Appearance, container: &Appearance) {
+        log::info!("You put {item} in the {container}");
+        self.log(|text| {
+            text.append(&format!("You put {item} in the {container}"));
+        });
+    }
+
+    pub fn container_locked(&mut self, container: &Appearance) {
+        log::info!("The {container} is locked");
+        self.log(|text| {
+            text.append(&format!("The {container} is locked"));
+        });
+    }
+
+    pub fn container_unlocked(&mut self, container: &Appearance) {
+        log::info!("You swipe a keycard and the {container} unlocks");
+        self.log(|text| {
+            text.append(&format!("You swipe a keycard and the {container} unlocks"));
+        });
+    }
+
+    pub fn opening_container(&mut self, container: &Appearance, turns_left: i32) {
+        log::info!("You work at the {container} ({turns_left} turns left)");
+        self.log(|text| {
+            text.append(&format!(
+                "You work at the {container} ({turns_left} turns left)"
+            ));
+        });
+    }
+
+    pub fn container_opened(&mut self, container: &Appearance) {
+        log::info!("You get the {container} open");
+        self.log(|text| {
+            text.append(&format!("You get the {container} open"));
+        });
+    }
+
+    pub fn player_drop(&mut self, item: &Appearance) {
+        log::info!("You dropped {item}");
+        self.log(|text| {
+            text.append(&format!("You dropped {item}"));
+        });
+    }
+
+    pub fn inventory_full(&mut self, item: &Appearance) {
+        log::info!("Your inventory is too full to pick up {item}");
+        self.log(|text| {
+            text.append(&format!("Your inventory is too full to pick up {item}"));
+        });
+    }
+
+    pub fn examine(
+        &mut self,
+        subject: &Appearance,
+        description: Option<&Description>,
+        durability: Option<&Durability>,
+        modifiers: Option<&StatModifiers>,
+    ) {
+        log::info!("You see {subject}");
+        self.log(|text| {
+            text.append(&format!("You see {subject}."));
+
+            if let Some(Description(description)) = description {
+                text.append(&format!(" {description}"));
+            }
+
+            if let Some(durability) = durability {
+                let (health, max_health) = durability.health();
+                text.append(&format!(" ({health}/{max_health})"));
+            }
+
+            if let Some(modifiers) = modifiers {
+                text.append(&format!(" [{modifiers}]"));
+            }
+        });
+    }
+
+    pub fn examine_nothing(&mut self) {
+        self.log(|text| {
+            text.append("There is nothing there.");
+        });
+    }
+
+    pub fn nanites_gained(&mut self, amount: i32, nanites: &Nanites) {
+        let balance = nanites.balance();
+        log::info!("You collected {amount} nanites ({balance} total)");
+        self.log(|text| {
+            text.append(&format!("You collected {amount} nanites ({balance} total)"));
+        });
+    }
+
+    pub fn nanites_spent(&mut self, amount: i32, purpose: &str, nanites: &Nanites) {
+        let balance = nanites.balance();
+        log::info!("You spent {amount} nanites on {purpose} ({balance} left)");
+        self.log(|text| {
+            text.append(&format!(
+                "You spent {amount} nanites on {purpose} ({balance} left)"
+            ));
+        });
+    }
+
+    pub fn recycled(&mut self, item: &Appearance, amount: i32, nanites: &Nanites) {
+        let balance = nanites.balance();
+        log::info!("You recycled {item} into {amount} nanites ({balance} total)");
+        self.log(|text| {
+            text.append(&format!(
+                "You recycled {item} into {amount} nanites ({balance} total)"
+            ));
+        });
+    }
+
+    pub fn repaired(&mut self, amount: i32) {
+        log::info!("Your suit was repaired for {amount} durability");
+        self.log(|text| {
+            text.append(&format!("Your suit was repaired for {amount} durability"));
+        });
+    }
+
+    pub fn suit_upgraded(&mut self, upgrade: SuitUpgrade, level: i32) {
+        let label = upgrade.label();
+        log::info!("Your suit's {label} was upgraded to level {level}");
+        self.log(|text| {
+            text.append(&format!(
+                "Your suit's {label} was upgraded to level {level}"
+            ));
+        });
+    }
+
+    pub fn weapon_upgraded(&mut self, weapon: &Appearance) {
+        log::info!("Your weapon was upgraded to {weapon}");
+        self.log(|text| {
+            text.append(&format!("Your weapon was upgraded to {weapon}"));
+        });
+    }
+
+    pub fn recharged(&mut self, amount: i32) {
+        log::info!("Your shield was recharged by {amount}");
+        self.log(|text| {
+            text.append(&format!("Your shield was recharged by {amount}"));
+        });
+    }
+
+    pub fn equip(&mut self, item: &Appearance) {
+        log::info!("You equipped {item}");
+        self.log(|text| {
+            text.append(&format!("You equipped {item}"));
+        });
+    }
+
+    pub fn unequip(&mut self, item: &Appearance) {
+        log::info!("You unequipped {item}");
+        self.log(|text| {
+            text.append(&format!("You unequipped {item}"));
+        });
+    }
+
+    pub fn no_free_mod_slot(&mut self) {
+        log::info!("You have no free mod slots");
+        self.log(|text| {
+            text.append("You have no free mod slots");
+        });
+    }
+
+    pub fn player_death(&mut self, nanites: Option<&Nanites>) {
         log::info!("You died! Resetting your health.");
         self.log(|text| {
             text.append("You died! Resetting your health.");
         });
+
+        if let Some(nanites) = nanites {
+            let (balance, collected) = (nanites.balance(), nanites.collected());
+            log::info!("Nanites: {balance} held, {collected} collected this run");
+            self.log(|text| {
+                text.append(&format!(
+                    "Nanites: {balance} held, {collected} collected this run"
+                ));
+            });
+        }
     }
 
     pub fn death(&mut self, victim: &Appearance) {
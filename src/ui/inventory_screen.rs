@@ -0,0 +1,372 @@
+use super::menu::Menu;
+use crate::{
+    game_mechanics::{Quickbar, Stack, INVENTORY_CAPACITY},
+    player_turn::PlayerTurn,
+    prelude::*,
+};
+use std::cmp::Reverse;
+
+/// Room for text inside the full screen menu
+const TEXT_WIDTH: usize = 45;
+
+#[derive(Clone, Copy, Default)]
+enum SortOrder {
+    #[default]
+    PickedUp,
+    Name,
+    Kind,
+}
+
+impl SortOrder {
+    fn label(self) -> &'static str {
+        match self {
+            Self::PickedUp => "Picked up",
+            Self::Name => "Name",
+            Self::Kind => "Kind",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Self::PickedUp => Self::Name,
+            Self::Name => Self::Kind,
+            Self::Kind => Self::PickedUp,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+enum ItemFilter {
+    #[default]
+    All,
+    Equipment,
+    Usables,
+}
+
+impl ItemFilter {
+    fn label(self) -> &'static str {
+        match self {
+            Self::All => "All",
+            Self::Equipment => "Equipment",
+            Self::Usables => "Usables",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Self::All => Self::Equipment,
+            Self::Equipment => Self::Usables,
+            Self::Usables => Self::All,
+        }
+    }
+
+    fn allows(self, item: &Item) -> bool {
+        match self {
+            Self::All => true,
+            Self::Equipment => *item == Item::Equipment,
+            Self::Usables => *item != Item::Equipment,
+        }
+    }
+}
+
+/// Remembered between visits to the inventory screen
+#[derive(Default)]
+pub struct InventoryView {
+    selected: usize,
+    sort: SortOrder,
+    filter: ItemFilter,
+}
+
+#[derive(SystemData)]
+struct InventoryViewData<'a> {
+    inventory: Read<'a, Inventory>,
+    view: Write<'a, InventoryView>,
+    quickbar: Write<'a, Quickbar>,
+    game_log: Write<'a, GameLog>,
+    loadout: Loadout<'a>,
+    items: ReadStorage<'a, Item>,
+    appearances: ReadStorage<'a, Appearance>,
+    descriptions: ReadStorage<'a, Description>,
+    usables: ReadStorage<'a, Usable>,
+    deals_damage: ReadStorage<'a, DealsDamage>,
+    provides_healing: ReadStorage<'a, ProvidesHealing>,
+    restores_charge: ReadStorage<'a, RestoresCharge>,
+    knockbacks: ReadStorage<'a, Knockback>,
+    charges: ReadStorage<'a, Charges>,
+    stacks: ReadStorage<'a, Stack>,
+    stat_modifiers: ReadStorage<'a, StatModifiers>,
+    recycle_values: ReadStorage<'a, RecycleValue>,
+}
+
+impl<'a> InventoryViewData<'a> {
+    /// The player's items, as currently sorted and filtered
+    fn listed(&self) -> Vec<Entity> {
+        let InventoryView { sort, filter, .. } = *self.view;
+
+        let mut listed: Vec<_> = self
+            .inventory
+            .items()
+            .filter(|&item| self.items.get(item).is_some_and(|i| filter.allows(i)))
+            .collect();
+
+        match sort {
+            SortOrder::PickedUp => {}
+            SortOrder::Name => listed.sort_by_key(|&item| self.name(item)),
+            SortOrder::Kind => listed.sort_by_key(|&item| {
+                let slot = self.loadout.slot(item).map(|slot| {
+                    EquipmentSlot::ALL
+                        .into_iter()
+                        .position(|s| s == slot)
+                        .unwrap_or_default()
+                });
+
+                // Equipment first, grouped by slot
+                (Reverse(slot.is_some()), slot, self.name(item))
+            }),
+        }
+
+        listed
+    }
+
+    fn name(&self, item: Entity) -> String {
+        self.appearances
+            .get(item)
+            .map_or_else(String::new, ToString::to_string)
+    }
+
+    fn list_entry(&self, item: Entity) -> String {
+        let label = self
+            .inventory
+            .labelled()
+            .find_map(|(label, i)| (i == item).then_some(label))
+            .unwrap_or(' ');
+
+        let mut entry = match self.appearances.get(item) {
+            Some(appearance) => format!("{label}: ({}) {appearance}", appearance.glyph),
+            None => format!("{label}:"),
+        };
+
+        if let Some(stack) = self.stacks.get(item).filter(|stack| stack.count() > 1) {
+            entry += &format!(" x{}", stack.count());
+        }
+
+        if let Some(charges) = self.charges.get(item) {
+            entry += &format!(" [{}/{}]", charges.current(), charges.max());
+        }
+
+        if self.loadout.is_equipped(item) {
+            entry += " (equipped)";
+        }
+
+        if let Some(slot) = self.quickbar.slot_of(item) {
+            entry += &format!(" <{}>", Quickbar::label(slot));
+        }
+
+        entry
+    }
+
+    /// Everything worth knowing about an item before using it
+    fn details(&self, item: Entity) -> Vec<String> {
+        let mut details = Vec::new();
+
+        if let Some(Description(description)) = self.descriptions.get(item) {
+            details.extend(wrap(description));
+        }
+
+        match (self.loadout.slot(item), self.items.get(item)) {
+            (Some(slot), _) => details.push(format!("Equipment: {}", slot.label())),
+            (None, Some(Item::Reusable)) => details.push("Reusable".to_string()),
+            (None, _) => details.push("Consumable".to_string()),
+        }
+
+        if let Some(DealsDamage(damage)) = self.deals_damage.get(item) {
+            details.push(format!("Damage: {}", damage.value()));
+        }
+
+        if let Some(&ProvidesHealing(amount)) = self.provides_healing.get(item) {
+            details.push(format!("Healing: {amount}"));
+        }
+
+        if let Some(&RestoresCharge(amount)) = self.restores_charge.get(item) {
+            details.push(format!("Restores {amount} charge"));
+        }
+
+        if let Some(&Knockback(distance)) = self.knockbacks.get(item) {
+            details.push(format!("Knockback: {distance}"));
+        }
+
+        match self.usables.get(item) {
+            Some(Usable::OnSelf) => details.push("Used on yourself".to_string()),
+            Some(Usable::OnTarget { range }) => details.push(format!("Range: {range}")),
+            Some(Usable::OnGround { range, radius }) => {
+                details.push(format!("Range: {range}, radius: {radius}"));
+            }
+            None => {}
+        }
+
+        if let Some(charges) = self.charges.get(item) {
+            details.push(format!("Charges: {}/{}", charges.current(), charges.max()));
+        }
+
+        if let Some(modifiers) = self.stat_modifiers.get(item) {
+            details.extend(wrap(&format!("Modifiers: {modifiers}")));
+        }
+
+        if let Some(&RecycleValue(value)) = self.recycle_values.get(item) {
+            details.push(format!("Recycles into {value} nanites"));
+        }
+
+        details
+    }
+
+    /// The keys that do something to the item
+    fn actions(&self, item: Entity) -> String {
+        let primary = if self.loadout.is_equipped(item) {
+            "E: Unequip  "
+        } else if self.loadout.is_equippable(item) {
+            "E: Equip  "
+        } else if self.usables.contains(item) {
+            "U: Use  "
+        } else {
+            ""
+        };
+
+        format!("{primary}D: Drop  T: Throw  X: Examine")
+    }
+
+    fn examine(&mut self, item: Entity) {
+        if let Some(appearance) = self.appearances.get(item) {
+            self.game_log.examine(
+                appearance,
+                self.descriptions.get(item),
+                None,
+                self.stat_modifiers.get(item),
+            );
+        }
+    }
+}
+
+/// Render the player's inventory over the map and act on the selected item
+pub fn inventory_screen(ctx: &BTerm, world: &mut World) -> RunState {
+    use {RunState::*, VirtualKeyCode::*};
+
+    world
+        .entry::<InventoryView>()
+        .or_insert_with(InventoryView::default);
+
+    let mut data = InventoryViewData::fetch(world);
+    let listed = data.listed();
+    data.view.selected = data.view.selected.min(listed.len().saturating_sub(1));
+    let selected = listed.get(data.view.selected).copied();
+
+    let InventoryView { sort, filter, .. } = *data.view;
+    let mut menu = Menu::new(format!(
+        "Inventory ({}/{INVENTORY_CAPACITY})",
+        data.inventory.count()
+    ))
+    .full_screen()
+    .text(format!(
+        "Sort: {}  Filter: {}",
+        sort.label(),
+        filter.label()
+    ))
+    .blank();
+
+    menu = listed.iter().fold(menu, |menu, &item| {
+        if Some(item) == selected {
+            menu.colored(format!("> {}", data.list_entry(item)), YELLOW)
+        } else {
+            menu.text(format!("  {}", data.list_entry(item)))
+        }
+    });
+
+    if let Some(item) = selected {
+        menu = data
+            .details(item)
+            .into_iter()
+            .fold(menu.blank(), |menu, line| menu.colored(line, GREY))
+            .blank()
+            .colored(data.actions(item), YELLOW)
+            .colored("1-0: Bind to quickbar", YELLOW);
+    } else {
+        menu = menu.text("Nothing to show");
+    }
+
+    menu.colored("W/S: Select  O: Sort  F: Filter  Esc: Close", YELLOW)
+        .draw();
+
+    if let (Some(slot), Some(item)) = (ctx.key.and_then(Quickbar::slot_for_key), selected) {
+        data.quickbar.bind(slot, item);
+    }
+
+    let action = match (ctx.key, selected) {
+        (Some(Up | W), _) => {
+            data.view.selected = data.view.selected.saturating_sub(1);
+            None
+        }
+        (Some(Down | S), _) => {
+            data.view.selected += 1;
+            None
+        }
+        (Some(O), _) => {
+            data.view.sort = sort.next();
+            None
+        }
+        (Some(F), _) => {
+            data.view.filter = filter.next();
+            data.view.selected = 0;
+            None
+        }
+        (Some(X), Some(item)) => {
+            data.examine(item);
+            None
+        }
+        (Some(key @ (U | E | D | T)), Some(item)) => Some((key, item)),
+        _ => None,
+    };
+
+    drop(data);
+
+    let res = match (action, ctx.key) {
+        (Some((key, item)), _) => {
+            let mut player_turn = PlayerTurn::fetch(world);
+
+            match key {
+                U => player_turn.use_item(item),
+                E => player_turn.toggle_equipped(item),
+                D => player_turn.drop_item(item),
+                _ => player_turn.throw_item(item),
+            }
+        }
+        (None, Some(Escape | I)) => Ok(AwaitingInput),
+        (None, _) => Ok(InventoryScreen),
+    };
+
+    world.maintain();
+
+    res.unwrap_or_else(|reason| {
+        log::warn!("{reason}");
+
+        InventoryScreen
+    })
+}
+
+/// Splits text into lines which fit on the screen
+fn wrap(text: &str) -> Vec<String> {
+    let mut lines = vec![String::new()];
+
+    for word in text.split_whitespace() {
+        let line = lines.last_mut().unwrap();
+
+        if line.is_empty() {
+            line.push_str(word);
+        } else if line.chars().count() + word.chars().count() < TEXT_WIDTH {
+            line.push(' ');
+            line.push_str(word);
+        } else {
+            lines.push(word.to_string());
+        }
+    }
+
+    lines
+}
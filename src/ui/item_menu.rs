@@ -0,0 +1,98 @@
+use super::menu::Menu;
+use crate::{
+    game_mechanics::Stack,
+    player_turn::{ItemAction, PlayerTurn},
+    prelude::*,
+};
+use anyhow::Context;
+
+/// Ask which item to drop, throw, etc.
+pub fn item_menu(action: ItemAction, ctx: &BTerm, world: &mut World) -> RunState {
+    use {RunState::*, VirtualKeyCode::*};
+
+    {
+        let inventory = world.fetch::<Inventory>();
+        let appearances = world.read_storage::<Appearance>();
+        let stacks = world.read_storage::<Stack>();
+        let menu = inventory
+            .labelled()
+            .filter_map(|(label, item)| Some((item, appearances.get(item)?, label)))
+            .fold(
+                Menu::new(action.prompt()),
+                |menu, (item, appearance, label)| match stacks
+                    .get(item)
+                    .filter(|stack| stack.count() > 1)
+                {
+                    Some(stack) => menu.option(label, format!("{appearance} x{}", stack.count())),
+                    None => menu.option(label, appearance),
+                },
+            );
+
+        menu.blank().option("Esc", "Cancel").draw();
+    }
+
+    let run_state = match ctx.key {
+        Some(Escape) => Ok(AwaitingInput),
+        Some(key) if letter_to_option(key) >= 0 => {
+            let mut player_turn = PlayerTurn::fetch(world);
+            player_turn
+                .inventory_item(letter_to_option(key) as usize)
+                .and_then(|item| player_turn.act_on_item(action, item))
+        }
+        _ => Ok(ChooseItem(action)),
+    };
+
+    world.maintain();
+
+    run_state.unwrap_or_else(|reason| {
+        log::warn!("{reason}");
+
+        ChooseItem(action)
+    })
+}
+
+/// Ask which item to pick up from the pile the player is standing on
+pub fn pickup_menu(ctx: &BTerm, world: &mut World) -> RunState {
+    use {RunState::*, VirtualKeyCode::*};
+
+    let mut player_turn = PlayerTurn::fetch(world);
+    let pile = player_turn.items_here();
+
+    {
+        let appearances = world.read_storage::<Appearance>();
+        let labels = (b'A'..=b'Z').map(|label| label as char);
+
+        let menu = pile
+            .iter()
+            .zip(labels)
+            .filter_map(|(&item, label)| Some((appearances.get(item)?, label)))
+            .fold(
+                Menu::new("Pick up which item?"),
+                |menu, (appearance, label)| menu.option(label, appearance),
+            );
+
+        menu.blank()
+            .option("Space", "Pick up all")
+            .option("Esc", "Cancel")
+            .draw();
+    }
+
+    let run_state = match ctx.key {
+        Some(Escape) => Ok(AwaitingInput),
+        Some(Space) => player_turn.pick_up(&pile),
+        Some(key) if letter_to_option(key) >= 0 => pile
+            .get(letter_to_option(key) as usize)
+            .context("no such item")
+            .and_then(|&item| player_turn.pick_up(&[item])),
+        _ => Ok(ChoosePickup),
+    };
+
+    drop(player_turn);
+    world.maintain();
+
+    run_state.unwrap_or_else(|reason| {
+        log::warn!("{reason}");
+
+        ChoosePickup
+    })
+}
@@ -21,6 +21,7 @@ impl<'a> System<'a> for RenderUILayoutSystem {
         );
         let player_stats_frame = Rect::with_size(0, 0, SIDEBAR_WIDTH, 7);
         let target_stats_frame = Rect::with_size(0, 7, SIDEBAR_WIDTH, 7);
+        let equipment_frame = Rect::with_size(0, 14, SIDEBAR_WIDTH, 7);
 
         let color = ColorPair::new(WHITE, BLACK);
 
@@ -29,6 +30,7 @@ impl<'a> System<'a> for RenderUILayoutSystem {
         draw_batch.draw_box(log_frame, color);
         draw_batch.draw_box(player_stats_frame, color);
         draw_batch.draw_box(target_stats_frame, color);
+        draw_batch.draw_box(equipment_frame, color);
 
         // Clean up the intersections
         draw_batch.print((SIDEBAR_WIDTH, 0).into(), "┬");
@@ -39,6 +41,8 @@ impl<'a> System<'a> for RenderUILayoutSystem {
         draw_batch.print((SIDEBAR_WIDTH, 7).into(), "┤");
         draw_batch.print((0, 14).into(), "├");
         draw_batch.print((SIDEBAR_WIDTH, 14).into(), "┤");
+        draw_batch.print((0, 21).into(), "├");
+        draw_batch.print((SIDEBAR_WIDTH, 21).into(), "┤");
 
         draw_batch.submit(0).unwrap();
     }
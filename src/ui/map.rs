@@ -1,12 +1,8 @@
-use super::{FULL_PAINT, MAP_WIDTH, TERM_WIDTH};
+use super::{BASE_SCALE, FULL_PAINT, MAP_CONSOLE, MAP_WIDTH, NO_ROTATION, TERM_WIDTH};
 use crate::prelude::*;
 
-const MAP_CONSOLE: usize = 1;
 const MAP_ORIGIN: PointF = PointF::new((TERM_WIDTH - MAP_WIDTH - 1) as f32, 1.75);
 
-const NO_ROTATION: Radians = Radians(0.0);
-const BASE_SCALE: PointF = PointF::new(1.0, 1.0);
-
 pub struct RenderMapSystem;
 
 impl<'a> System<'a> for RenderMapSystem {
@@ -17,17 +13,34 @@ impl<'a> System<'a> for RenderMapSystem {
         Entities<'a>,
         ReadStorage<'a, Coordinate>,
         ReadStorage<'a, Appearance>,
+        ReadStorage<'a, Durability>,
         ReadStorage<'a, Viewshed>,
         ReadStorage<'a, Target>,
     );
 
     fn run(
         &mut self,
-        (map, player, targeting_reticule, entities, coordinates, appearances, viewsheds, targets): Self::SystemData,
+        (
+            map,
+            player,
+            targeting_reticule,
+            entities,
+            coordinates,
+            appearances,
+            durabilities,
+            viewsheds,
+            targets,
+        ): Self::SystemData,
     ) {
         let player_viewshed = viewsheds.get(*player).unwrap();
         let player_target = targets.get(*player);
 
+        // Everything the aimed effect would reach
+        let blast_area = targeting_reticule
+            .as_ref()
+            .filter(|reticule| reticule.is_aiming())
+            .map(|reticule| reticule.blast_area(&map));
+
         let mut draw_batch = DrawBatch::new();
 
         draw_batch.target(MAP_CONSOLE);
@@ -53,44 +66,52 @@ impl<'a> System<'a> for RenderMapSystem {
         }
 
         if let Some(targeting_reticule) = targeting_reticule {
-            for &coord in targeting_reticule.coordinates.iter() {
+            let mut highlight = |coord: Coordinate, z_order: i32, color: (u8, u8, u8)| {
                 draw_batch.set_fancy(
                     MAP_ORIGIN + coord.into(),
-                    1,
+                    z_order,
                     NO_ROTATION,
                     BASE_SCALE,
-                    ColorPair::new(BLUE, BLACK),
+                    ColorPair::new(color, BLACK),
                     to_cp437('█'),
                 );
-            }
+            };
 
-            for coord in
-                map.area_of_effect(targeting_reticule.cursor, targeting_reticule.aoe_radius)
-            {
-                let color = if coord == targeting_reticule.cursor {
-                    ColorPair::new(WHITE, BLACK)
-                } else {
-                    ColorPair::new(ORANGE, BLACK)
-                };
+            if let Some(blast_area) = &blast_area {
+                for &coord in targeting_reticule.coordinates.iter() {
+                    highlight(coord, 1, BLUE);
+                }
 
-                draw_batch.set_fancy(
-                    MAP_ORIGIN + coord.into(),
-                    2,
-                    NO_ROTATION,
-                    BASE_SCALE,
-                    color,
-                    to_cp437('█'),
-                );
+                for coord in targeting_reticule.projectile_path(&map) {
+                    highlight(coord, 2, YELLOW);
+                }
+
+                for &coord in blast_area {
+                    highlight(coord, 3, ORANGE);
+                }
             }
+
+            highlight(targeting_reticule.cursor, 4, WHITE);
         }
 
         for (entity, &coord, appearance) in (&entities, &coordinates, &appearances).join() {
             let mut color = appearance.color;
 
-            if player_target.map_or(false, |&Target(target)| entity == target) {
+            if player_target.is_some_and(|&Target(target)| entity == target) {
                 color.bg = RGBA::named(WHITE);
             }
 
+            // Highlight anyone caught in the blast, especially the player
+            if durabilities.contains(entity)
+                && blast_area.as_ref().is_some_and(|a| a.contains(&coord))
+            {
+                color = if entity == *player {
+                    ColorPair::new(WHITE, RED)
+                } else {
+                    ColorPair::new(color.fg, DARK_RED)
+                };
+            }
+
             if player_viewshed.is_visible(coord) {
                 draw_batch.set_fancy(
                     MAP_ORIGIN + coord.into(),
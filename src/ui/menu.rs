@@ -0,0 +1,132 @@
+use super::{
+    BASE_SCALE, FULL_PAINT, MAP_CONSOLE, MAP_HEIGHT, MAP_WIDTH, NO_ROTATION, SIDEBAR_WIDTH,
+    TERM_WIDTH,
+};
+use crate::prelude::*;
+use std::fmt::Display;
+
+/// Menus are drawn on the map console above everything else.
+const MENU_Z_ORDER: i32 = 1000;
+const MENU_TOP: i32 = 4;
+
+/// A boxed list of text lines drawn over the map
+pub struct Menu {
+    title: String,
+    lines: Vec<(String, RGBA)>,
+    /// Cover the whole map rather than fitting the box to the text
+    full_screen: bool,
+}
+
+impl Menu {
+    pub fn new(title: impl ToString) -> Self {
+        Self {
+            title: title.to_string(),
+            lines: Vec::new(),
+            full_screen: false,
+        }
+    }
+
+    pub fn full_screen(mut self) -> Self {
+        self.full_screen = true;
+        self
+    }
+
+    pub fn text(self, text: impl ToString) -> Self {
+        self.colored(text, WHITE)
+    }
+
+    pub fn colored(mut self, text: impl ToString, color: (u8, u8, u8)) -> Self {
+        self.lines.push((text.to_string(), RGBA::named(color)));
+        self
+    }
+
+    pub fn option(mut self, key: impl Display, text: impl Display) -> Self {
+        self.lines
+            .push((format!("{key}: {text}"), RGBA::named(YELLOW)));
+        self
+    }
+
+    pub fn blank(self) -> Self {
+        self.text("")
+    }
+
+    pub fn draw(&self) {
+        let frame = if self.full_screen {
+            Rect::with_size(
+                SIDEBAR_WIDTH,
+                0,
+                TERM_WIDTH - SIDEBAR_WIDTH - 1,
+                MAP_HEIGHT + 1,
+            )
+        } else {
+            let width = self
+                .lines
+                .iter()
+                .map(|(line, _)| line.chars().count())
+                .chain([self.title.chars().count()])
+                .max()
+                .unwrap_or_default() as i32
+                + 4;
+            let height = self.lines.len() as i32 + 4;
+
+            let left = TERM_WIDTH - MAP_WIDTH / 2 - width / 2 - 1;
+            Rect::with_size(left, MENU_TOP, width - 1, height - 1)
+        };
+        let (left, top, width) = (frame.x1, frame.y1, frame.width() + 1);
+
+        let mut draw_batch = DrawBatch::new();
+        draw_batch.target(MAP_CONSOLE);
+
+        for y in frame.y1..=frame.y2 {
+            for x in frame.x1..=frame.x2 {
+                let glyph = match (x, y) {
+                    _ if (x, y) == (frame.x1, frame.y1) => '┌',
+                    _ if (x, y) == (frame.x2, frame.y1) => '┐',
+                    _ if (x, y) == (frame.x1, frame.y2) => '└',
+                    _ if (x, y) == (frame.x2, frame.y2) => '┘',
+                    _ if y == frame.y1 || y == frame.y2 => '─',
+                    _ if x == frame.x1 || x == frame.x2 => '│',
+                    _ => ' ',
+                };
+
+                print_fancy(&mut draw_batch, x, y, glyph, RGBA::named(WHITE));
+            }
+        }
+
+        let title_left = left + (width - self.title.chars().count() as i32) / 2;
+        print_line(&mut draw_batch, title_left, top, &self.title, WHITE.into());
+
+        for (y, (line, color)) in (top + 2..frame.y2).zip(self.lines.iter()) {
+            print_line(&mut draw_batch, left + 2, y, line, *color);
+        }
+
+        draw_batch.submit(3 * FULL_PAINT).unwrap();
+    }
+}
+
+/// Asks before throwing something which would hit the player
+pub fn confirm_self_damage() {
+    Menu::new("Caution")
+        .text("You are inside the blast radius.")
+        .blank()
+        .option('Y', "Throw anyway")
+        .option('N', "Keep aiming")
+        .draw();
+}
+
+fn print_line(draw_batch: &mut DrawBatch, x: i32, y: i32, line: &str, color: RGBA) {
+    for (x, glyph) in (x..).zip(line.chars()) {
+        print_fancy(draw_batch, x, y, glyph, color);
+    }
+}
+
+fn print_fancy(draw_batch: &mut DrawBatch, x: i32, y: i32, glyph: char, color: RGBA) {
+    draw_batch.set_fancy(
+        PointF::new(x as f32, y as f32),
+        MENU_Z_ORDER,
+        NO_ROTATION,
+        BASE_SCALE,
+        ColorPair::new(color, BLACK),
+        to_cp437(glyph),
+    );
+}
@@ -1,18 +1,30 @@
 mod appearance;
+mod character_sheet;
+mod container_menu;
 mod game_log;
+mod inventory_screen;
+mod item_menu;
 mod layout;
 mod main_menu;
 mod map;
+mod menu;
 mod sidebar;
+mod station_menu;
 
 use crate::prelude::*;
 
 pub use appearance::Appearance;
+pub use character_sheet::character_sheet;
+pub use container_menu::container_menu;
 pub use game_log::{GameLog, RenderGameLogSystem};
+pub use inventory_screen::inventory_screen;
+pub use item_menu::{item_menu, pickup_menu};
 pub use layout::RenderUILayoutSystem;
 pub use main_menu::main_menu;
 pub use map::RenderMapSystem;
-pub use sidebar::{RenderInventorySystem, RenderPlayerStatsSystem};
+pub use menu::confirm_self_damage;
+pub use sidebar::{RenderEquipmentSystem, RenderInventorySystem, RenderPlayerStatsSystem};
+pub use station_menu::station_menu;
 
 pub const TERM_WIDTH: i32 = 80;
 pub const TERM_HEIGHT: i32 = 60;
@@ -24,6 +36,11 @@ const SIDEBAR_WIDTH: i32 = TERM_WIDTH - MAP_WIDTH - 2;
 
 const FULL_PAINT: usize = (TERM_WIDTH * TERM_HEIGHT) as usize;
 
+const MAP_CONSOLE: usize = 1;
+
+const NO_ROTATION: Radians = Radians(0.0);
+const BASE_SCALE: PointF = PointF::new(1.0, 1.0);
+
 pub fn setup() -> BResult<BTerm> {
     BTermBuilder::simple(TERM_WIDTH, TERM_HEIGHT)?
         .with_fancy_console(TERM_WIDTH, TERM_HEIGHT, "terminal8x8.png")
@@ -37,6 +54,7 @@ pub fn dispatcher<'a, 'b>(_world: &mut World) -> Dispatcher<'a, 'b> {
         .with(RenderUILayoutSystem, "render_ui_layout", &[])
         .with(RenderMapSystem, "render_map", &[])
         .with(RenderPlayerStatsSystem, "render_stats", &[])
+        .with(RenderEquipmentSystem, "render_equipment", &[])
         .with(RenderInventorySystem, "render_inventory", &[])
         .with(RenderGameLogSystem, "render_game_log", &[])
         .build()
@@ -1,9 +1,13 @@
 use super::{FULL_PAINT, SIDEBAR_WIDTH};
-use crate::prelude::*;
+use crate::{
+    game_mechanics::{thrown_damage, Quickbar, Stack},
+    prelude::*,
+};
 
 const PLAYER_STATS_ORIGIN: Point = Point::constant(2, 2);
 const TARGET_STATS_ORIGIN: Point = Point::constant(2, 9);
-const INVENTORY_ORIGIN: Point = Point::constant(2, 16);
+const EQUIPMENT_ORIGIN: Point = Point::constant(2, 16);
+const INVENTORY_ORIGIN: Point = Point::constant(2, 23);
 const WIDTH: i32 = SIDEBAR_WIDTH - 3;
 
 pub struct RenderPlayerStatsSystem;
@@ -12,11 +16,12 @@ impl<'a> System<'a> for RenderPlayerStatsSystem {
     type SystemData = (
         ReadExpect<'a, Entity>,
         ReadStorage<'a, Durability>,
+        ReadStorage<'a, Nanites>,
         ReadStorage<'a, Target>,
         ReadStorage<'a, Appearance>,
     );
 
-    fn run(&mut self, (player, durabilities, targets, appearances): Self::SystemData) {
+    fn run(&mut self, (player, durabilities, nanites, targets, appearances): Self::SystemData) {
         let mut draw_batch = DrawBatch::new();
 
         let player_durability = durabilities.get(*player).unwrap();
@@ -35,6 +40,14 @@ impl<'a> System<'a> for RenderPlayerStatsSystem {
             ColorPair::new(YELLOW, BLACK),
         );
 
+        if let Some(nanites) = nanites.get(*player) {
+            draw_batch.print_color(
+                PLAYER_STATS_ORIGIN,
+                format!("$ {}", nanites.balance()),
+                ColorPair::new(CYAN, BLACK),
+            );
+        }
+
         draw_batch.bar_horizontal(
             (PLAYER_STATS_ORIGIN.x, PLAYER_STATS_ORIGIN.y + 2).into(),
             WIDTH,
@@ -96,22 +109,111 @@ impl<'a> System<'a> for RenderPlayerStatsSystem {
     }
 }
 
+pub struct RenderEquipmentSystem;
+
+impl<'a> System<'a> for RenderEquipmentSystem {
+    type SystemData = (
+        ReadExpect<'a, Entity>,
+        Loadout<'a>,
+        ReadStorage<'a, ModSlots>,
+        ReadStorage<'a, Appearance>,
+    );
+
+    fn run(&mut self, (player, loadout, mod_slots, appearances): Self::SystemData) {
+        let mut text = TextBuilder::empty();
+
+        for slot in EquipmentSlot::ALL {
+            text.fg(WHITE)
+                .append(&format!("{:<8}", format!("{}:", slot.label())));
+
+            if slot == EquipmentSlot::Mod {
+                let equipped = loadout.in_slot(*player, slot).count();
+                let available = mod_slots.get(*player).map_or(0, |&ModSlots(n)| n);
+                text.append(&format!("{equipped}/{available}"));
+            } else {
+                match loadout
+                    .in_slot(*player, slot)
+                    .find_map(|item| appearances.get(item))
+                {
+                    Some(appearance) => full_name(&mut text, appearance),
+                    None => {
+                        text.fg(GREY).append("-");
+                    }
+                }
+            }
+
+            text.ln();
+        }
+
+        let mut draw_batch = DrawBatch::new();
+
+        let mut text_block = TextBlock::new(EQUIPMENT_ORIGIN.x, EQUIPMENT_ORIGIN.y, WIDTH, 5);
+        text_block.print(&text).ok(); // Ignore OutOfSpace
+        text_block.render_to_draw_batch(&mut draw_batch);
+
+        draw_batch.submit(2 * FULL_PAINT).unwrap();
+    }
+}
+
 pub struct RenderInventorySystem;
 
 impl<'a> System<'a> for RenderInventorySystem {
-    type SystemData = (Read<'a, Inventory>, ReadStorage<'a, Appearance>);
+    type SystemData = (
+        Read<'a, Inventory>,
+        Read<'a, Quickbar>,
+        Loadout<'a>,
+        ReadStorage<'a, Appearance>,
+        ReadStorage<'a, Charges>,
+        ReadStorage<'a, Stack>,
+        BlastPreview<'a>,
+    );
 
-    fn run(&mut self, (player_inventory, appearances): Self::SystemData) {
+    fn run(
+        &mut self,
+        (
+            player_inventory,
+            quickbar,
+            loadout,
+            appearances,
+            charges,
+            stacks,
+            blast_preview,
+        ): Self::SystemData,
+    ) {
         let mut text = TextBuilder::empty();
 
-        text.append("Inventory:").ln().ln();
-
-        let labels = (b'A'..=b'Z').map(|label| label as char);
+        // While aiming, the space is used to show who the blast would hit instead
+        let aiming = blast_preview.render(&mut text);
+        if !aiming {
+            text.append("Inventory:").ln().ln();
+        }
 
-        for (&item, label) in player_inventory.0.iter().zip(labels) {
+        for (label, item) in player_inventory.labelled().filter(|_| !aiming) {
             if let Some(appearance) = appearances.get(item) {
-                text.fg(WHITE).append(&format!("{label}: "));
+                // Equipped items are listed with a highlighted label
+                let label_color = if loadout.is_equipped(item) {
+                    GREEN
+                } else {
+                    WHITE
+                };
+                text.fg(label_color).append(&format!("{label}: "));
                 full_name(&mut text, appearance);
+
+                if let Some(stack) = stacks.get(item).filter(|stack| stack.count() > 1) {
+                    text.fg(GREY).append(&format!(" x{}", stack.count()));
+                }
+
+                if let Some(charges) = charges.get(item) {
+                    let color = if charges.is_empty() { RED } else { GREY };
+                    text.fg(color)
+                        .append(&format!(" [{}/{}]", charges.current(), charges.max()));
+                }
+
+                if let Some(slot) = quickbar.slot_of(item) {
+                    text.fg(YELLOW)
+                        .append(&format!(" <{}>", Quickbar::label(slot)));
+                }
+
                 text.ln();
             }
         }
@@ -126,14 +228,69 @@ impl<'a> System<'a> for RenderInventorySystem {
     }
 }
 
-fn full_name(
-    text: &mut TextBuilder,
-    Appearance {
-        name, color, glyph, ..
-    }: &Appearance,
-) {
+#[derive(SystemData)]
+pub struct BlastPreview<'a> {
+    map: ReadExpect<'a, Map>,
+    player: ReadExpect<'a, Entity>,
+    targeting_reticule: Option<Read<'a, TargetingReticule>>,
+    entities: Entities<'a>,
+    positions: ReadStorage<'a, Coordinate>,
+    durabilities: ReadStorage<'a, Durability>,
+    deals_damage: ReadStorage<'a, DealsDamage>,
+    usables: ReadStorage<'a, Usable>,
+    viewsheds: ReadStorage<'a, Viewshed>,
+    appearances: ReadStorage<'a, Appearance>,
+}
+
+impl<'a> BlastPreview<'a> {
+    /// Lists the expected damage to everyone visible in the blast. Returns
+    /// false when nothing is being aimed.
+    fn render(&self, text: &mut TextBuilder) -> bool {
+        let Some(reticule) = self.targeting_reticule.as_ref() else {
+            return false;
+        };
+        let Some(effect) = reticule.effect else {
+            return false;
+        };
+
+        let damage = thrown_damage(self.usables.get(effect), self.deals_damage.get(effect));
+        let blast_area = reticule.blast_area(&self.map);
+        let viewshed = self.viewsheds.get(*self.player).unwrap();
+
+        text.append("Blast preview:").ln().ln();
+
+        for (entity, pos, durability, appearance) in (
+            &self.entities,
+            &self.positions,
+            &self.durabilities,
+            &self.appearances,
+        )
+            .join()
+        {
+            if !blast_area.contains(pos) || !viewshed.is_visible(*pos) {
+                continue;
+            }
+
+            full_name(text, appearance);
+
+            let color = if entity == *self.player { RED } else { WHITE };
+            if damage > 0 {
+                let expected = durability.expected_damage(damage);
+                text.fg(color).append(&format!(": {expected} damage")).ln();
+            } else {
+                text.fg(color).append(": caught in blast").ln();
+            }
+        }
+
+        true
+    }
+}
+
+fn full_name(text: &mut TextBuilder, appearance: &Appearance) {
+    let Appearance { color, glyph, .. } = appearance;
+
     text.fg(WHITE).append("(");
     text.fg(color.fg).append(&glyph.to_string());
     text.fg(WHITE).append(") ");
-    text.fg(color.fg).append(name);
+    text.fg(color.fg).append(&appearance.to_string());
 }
@@ -0,0 +1,108 @@
+use super::menu::Menu;
+use crate::{
+    game_mechanics::{Station, StationUsage},
+    prelude::*,
+};
+
+/// Render a station's menu and handle the player's choice
+pub fn station_menu(station: Entity, ctx: &BTerm, world: &mut World) -> RunState {
+    use {RunState::*, Station::*, VirtualKeyCode::*};
+
+    let kind = *world.read_storage::<Station>().get(station).unwrap();
+    let title = world
+        .read_storage::<Appearance>()
+        .get(station)
+        .map_or_else(String::new, ToString::to_string);
+
+    let mut usage = StationUsage::fetch(world);
+
+    let menu = Menu::new(title)
+        .text(format!("Nanites: {}", usage.balance()))
+        .blank();
+
+    let menu = match kind {
+        Repair => menu.option(
+            'R',
+            format!("Repair suit ({} nanites)", usage.repair_cost()),
+        ),
+        Recharge => {
+            let menu = menu
+                .option(
+                    "Space",
+                    format!("Recharge shield ({} nanites)", usage.recharge_cost()),
+                )
+                .blank();
+
+            list_items(menu, usage.rechargeables(), "no charges")
+        }
+        Recycle => list_items(menu, usage.recyclables(), "can't recycle"),
+        Upgrade => {
+            let menu = usage.suit_upgrades().zip(b'A'..).fold(
+                menu,
+                |menu, ((upgrade, level, cost), label)| {
+                    let upgrade = format!(
+                        "{} {level}/{} ({})",
+                        upgrade.label(),
+                        upgrade.max_level(),
+                        upgrade.effect()
+                    );
+                    match cost {
+                        Some(cost) => {
+                            menu.option(label as char, format!("{upgrade}: {cost} nanites"))
+                        }
+                        None => menu.text(format!("   {upgrade}: maxed")),
+                    }
+                },
+            );
+
+            let cost = usage.weapon_upgrade_cost();
+            menu.blank()
+                .option('M', format!("Upgrade melee weapon ({cost} nanites)"))
+                .option('R', format!("Upgrade ranged weapon ({cost} nanites)"))
+        }
+    };
+
+    menu.blank().option("Esc", "Leave").draw();
+
+    let res = ctx.key.map_or(Ok(()), |key| match (kind, key) {
+        (Repair, R) => usage.repair(),
+        (Recharge, Space) => usage.recharge_shield(),
+        (Recharge, key) if letter_to_option(key) >= 0 => {
+            usage.recharge_item(letter_to_option(key) as usize)
+        }
+        (Upgrade, M) => usage.upgrade_weapon(EquipmentSlot::MeleeWeapon),
+        (Upgrade, R) => usage.upgrade_weapon(EquipmentSlot::RangedWeapon),
+        (Upgrade, key) if letter_to_option(key) >= 0 => {
+            usage.upgrade_suit(letter_to_option(key) as usize)
+        }
+        (Recycle, key) if letter_to_option(key) >= 0 => {
+            usage.recycle(letter_to_option(key) as usize)
+        }
+        _ => Ok(()),
+    });
+
+    if let Err(reason) = res {
+        log::warn!("{reason}");
+    }
+
+    drop(usage);
+    world.maintain();
+
+    match ctx.key {
+        Some(Escape) => AwaitingInput,
+        _ => UseStation(station),
+    }
+}
+
+/// Lists the player's inventory along with the nanites each item is worth or
+/// costs, labelling only the items the station can do something with.
+fn list_items<'a>(
+    menu: Menu,
+    items: impl Iterator<Item = (char, &'a Appearance, Option<i32>)>,
+    unavailable: &str,
+) -> Menu {
+    items.fold(menu, |menu, (label, appearance, nanites)| match nanites {
+        Some(nanites) => menu.option(label, format!("{appearance} ({nanites} nanites)")),
+        None => menu.text(format!("   {appearance} ({unavailable})")),
+    })
+}